@@ -0,0 +1,90 @@
+use bencher::{benchmark_group, benchmark_main, black_box, Bencher};
+use bytes::BytesMut;
+use masterpower_api::codec::Codec;
+use masterpower_api::command::Response;
+use masterpower_api::commands::qpigs::{QPIGSResponse, QPIGS};
+use masterpower_api::commands::qpiri::{QPIRIResponse, QPIRI};
+use masterpower_api::frame::{self, crc};
+use masterpower_api::pi18::commands::gs::GSResponse;
+use tokio_util::codec::Decoder;
+
+const QPIGS_PAYLOAD: &[u8] = b"001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0000 074.9 27.12 00005 10110110 17 04 00010 100";
+const QPIRI_PAYLOAD: &[u8] = b"230.0 13.0 230.0 50.0 13.0 3000 2400 24.0 23.0 21.0 28.2 27.0 0 30 60 0 0 0 - 01 1 0 27.0 0 0";
+const PI18_GS_PAYLOAD: &[u8] = b"2366,499,2299,500,0482,0404,008,536,000,000,000,012,100,044,038,000,1080,0000,2475,0000,0,2,0,1,1,2,1,0";
+
+// Number of inverters answering one polling round.
+const UNITS: usize = 64;
+
+fn framed(payload: &[u8]) -> Vec<u8> {
+    let mut res = vec![b'('];
+    res.extend_from_slice(payload);
+    let crc_sum = crc(&res);
+    res.extend_from_slice(&crc_sum.to_be_bytes());
+    res.push(b'\r');
+
+    res
+}
+
+fn qpigs_payload(b: &mut Bencher) {
+    b.bytes = QPIGS_PAYLOAD.len() as u64;
+    b.iter(|| QPIGSResponse::decode(black_box(QPIGS_PAYLOAD)).unwrap());
+}
+
+fn qpiri_payload(b: &mut Bencher) {
+    b.bytes = QPIRI_PAYLOAD.len() as u64;
+    b.iter(|| QPIRIResponse::decode(black_box(QPIRI_PAYLOAD)).unwrap());
+}
+
+fn pi18_gs_payload(b: &mut Bencher) {
+    b.bytes = PI18_GS_PAYLOAD.len() as u64;
+    b.iter(|| GSResponse::decode(black_box(PI18_GS_PAYLOAD)).unwrap());
+}
+
+// A polling round of QPIGS replies from every unit, picked straight out of the receive buffer.
+fn qpigs_frames(b: &mut Bencher) {
+    let src = framed(QPIGS_PAYLOAD).repeat(UNITS);
+    b.bytes = src.len() as u64;
+    b.iter(|| {
+        let mut src = black_box(&src[..]);
+        while let Some(item) = frame::decode_pi30(src) {
+            src = &src[item.consumed..];
+            black_box(item.decode::<QPIGS>().unwrap());
+        }
+    });
+}
+
+// The same round going through the tokio codec and its buffer.
+fn qpigs_codec(b: &mut Bencher) {
+    let src = framed(QPIGS_PAYLOAD).repeat(UNITS);
+    let mut codec = Codec::<QPIGS>::new();
+    b.bytes = src.len() as u64;
+    b.iter(|| {
+        let mut buf = BytesMut::from(black_box(&src[..]));
+        while let Some(item) = codec.decode(&mut buf).unwrap() {
+            black_box(item);
+        }
+    });
+}
+
+fn qpiri_codec(b: &mut Bencher) {
+    let src = framed(QPIRI_PAYLOAD).repeat(UNITS);
+    let mut codec = Codec::<QPIRI>::new();
+    b.bytes = src.len() as u64;
+    b.iter(|| {
+        let mut buf = BytesMut::from(black_box(&src[..]));
+        while let Some(item) = codec.decode(&mut buf).unwrap() {
+            black_box(item);
+        }
+    });
+}
+
+benchmark_group!(
+    benches,
+    qpigs_payload,
+    qpiri_payload,
+    pi18_gs_payload,
+    qpigs_frames,
+    qpigs_codec,
+    qpiri_codec
+);
+benchmark_main!(benches);
@@ -0,0 +1,123 @@
+//! Decoding entry points for the fuzz targets.
+//!
+//! Run with `cargo +nightly fuzz run codec` (or `response`, `modbus`) from the crate root. The
+//! same entry points are driven over a fixed corpus by `tests/fuzz_corpus.rs`, so regressions show
+//! up in a plain `cargo test` too.
+
+use bytes::BytesMut;
+use masterpower_api::command::{Command, Response};
+use masterpower_api::modbus::registers;
+use masterpower_api::{codec, commands, modbus, pi17, pi18};
+use tokio_util::codec::Decoder;
+
+macro_rules! each_command {
+    ($pi30:ident, $pi17:ident, $data:expr) => {
+        $pi30::<commands::bms::BMS>($data);
+        $pi30::<commands::dat::DAT>($data);
+        $pi30::<commands::f::F>($data);
+        $pi30::<commands::pbeqa::PBEQA>($data);
+        $pi30::<commands::pbeqe::PBEQE>($data);
+        $pi30::<commands::pbeqot::PBEQOT>($data);
+        $pi30::<commands::pbeqp::PBEQP>($data);
+        $pi30::<commands::pbeqt::PBEQT>($data);
+        $pi30::<commands::pbeqv::PBEQV>($data);
+        $pi30::<commands::pbt::PBT>($data);
+        $pi30::<commands::pgr::PGR>($data);
+        $pi30::<commands::pledb::PLEDB>($data);
+        $pi30::<commands::pledc::PLEDC>($data);
+        $pi30::<commands::plede::PLEDE>($data);
+        $pi30::<commands::pledm::PLEDM>($data);
+        $pi30::<commands::pleds::PLEDS>($data);
+        $pi30::<commands::pledt::PLEDT>($data);
+        $pi30::<commands::popm::POPM>($data);
+        $pi30::<commands::qbeqi::QBEQI>($data);
+        $pi30::<commands::qboot::QBOOT>($data);
+        $pi30::<commands::qfr::QFR>($data);
+        $pi30::<commands::qgmn::QGMN>($data);
+        $pi30::<commands::qid::QID>($data);
+        $pi30::<commands::qled::QLED>($data);
+        $pi30::<commands::qmn::QMN>($data);
+        $pi30::<commands::qmod::QMOD>($data);
+        $pi30::<commands::qopm::QOPM>($data);
+        $pi30::<commands::qpgs::QPGS>($data);
+        $pi30::<commands::qpi::QPI>($data);
+        $pi30::<commands::qpigs::QPIGS>($data);
+        $pi30::<commands::qpigs2::QPIGS2>($data);
+        $pi30::<commands::qpiri::QPIRI>($data);
+        $pi30::<commands::qpiws::QPIWS>($data);
+        $pi30::<commands::qsid::QSID>($data);
+        $pi30::<commands::qt::QT>($data);
+        $pi30::<commands::qvfw::QVFW>($data);
+        $pi30::<commands::qvfw2::QVFW2>($data);
+        $pi30::<commands::qvfw3::QVFW3>($data);
+        $pi30::<commands::v::V>($data);
+        $pi17::<pi17::commands::et::ET>($data);
+        $pi17::<pi17::commands::gs::GS>($data);
+        $pi17::<pi17::commands::mode::MOD>($data);
+        $pi17::<pi17::commands::pi::PI>($data);
+        $pi17::<pi17::commands::piri::PIRI>($data);
+        $pi17::<pi17::commands::ws::WS>($data);
+        $pi17::<pi18::commands::et::ET>($data);
+        $pi17::<pi18::commands::fws::FWS>($data);
+        $pi17::<pi18::commands::gs::GS>($data);
+        $pi17::<pi18::commands::id::ID>($data);
+        $pi17::<pi18::commands::mode::MOD>($data);
+        $pi17::<pi18::commands::pi::PI>($data);
+        $pi17::<pi18::commands::piri::PIRI>($data);
+        $pi17::<pi18::commands::vfw::VFW>($data);
+    };
+}
+
+/// Feed `data` as a receive buffer to the codec of every command.
+pub fn codec(data: &[u8]) {
+    each_command!(pi30_codec, pi17_codec, data);
+}
+
+/// Decode `data` as the payload of every response.
+pub fn response(data: &[u8]) {
+    each_command!(payload, payload, data);
+}
+
+/// Feed `data` as a receive buffer to the Modbus RTU codec, and decode it as register blocks.
+pub fn modbus(data: &[u8]) {
+    drain(modbus::codec::Codec::new(1), data);
+
+    let block = data
+        .chunks_exact(2)
+        .map(|x| u16::from_be_bytes([x[0], x[1]]))
+        .collect::<Vec<_>>();
+    let _ = registers::device_mode(&block);
+    let _ = registers::device_mode_lenient(&block);
+    let _ = registers::general_status(&block);
+    let _ = registers::rating_information(&block);
+    let _ = registers::rating_information_lenient(&block);
+}
+
+fn pi30_codec<C: Command>(data: &[u8]) {
+    drain(codec::Codec::<C>::new(), data);
+}
+
+fn pi17_codec<C: Command>(data: &[u8]) {
+    drain(pi17::codec::Codec::<C>::new(), data);
+}
+
+fn payload<C: Command>(data: &[u8]) {
+    let _ = C::Response::decode(data);
+    let _ = C::Response::decode_lenient(data);
+}
+
+fn drain<D: Decoder>(mut codec: D, data: &[u8]) {
+    let mut buf = BytesMut::from(data);
+
+    // Every frame is dropped from the buffer whether it decodes or not, so a decoder that keeps
+    // returning items without consuming anything would hang the connection.
+    while !buf.is_empty() {
+        let len = buf.len();
+        if let Ok(None) = codec.decode(&mut buf) {
+            break;
+        }
+        assert!(buf.len() < len, "decoder made no progress");
+    }
+
+    let _ = codec.decode_eof(&mut buf);
+}
@@ -0,0 +1,147 @@
+use crate::commands::qmod::QMOD;
+use crate::commands::qpigs::QPIGS;
+use crate::commands::qpiri::QPIRI;
+use crate::commands::qpiws::QPIWS;
+use crate::error::{Error, ErrorKind, Result};
+use crate::inverter::Inverter;
+use crate::modbus::client::Client;
+use crate::pi17::commands::gs::GS;
+use crate::pi17::commands::mode::MOD;
+use crate::pi17::commands::ws::WS;
+use crate::pi18::commands::fws::FWS;
+use crate::pi18::commands::gs::GS as PI18GS;
+use crate::pi18::commands::mode::MOD as PI18MOD;
+use crate::pi18::commands::piri::PIRI as PI18PIRI;
+use crate::settings::InverterSettings;
+use crate::status::InverterStatus;
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Source of protocol-independent status and settings.
+#[async_trait]
+pub trait Backend {
+    async fn status(&mut self) -> Result<InverterStatus>;
+    async fn settings(&mut self) -> Result<InverterSettings>;
+}
+
+// Dispatch on the negotiated protocol, assuming PI30 when negotiation has not been done.
+#[async_trait]
+impl<S> Backend for Inverter<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    async fn status(&mut self) -> Result<InverterStatus> {
+        Ok(match self.capabilities().map(|x| x.protocol_id) {
+            Some(17) => {
+                let gs = self.execute_pi17::<GS>(()).await?;
+                let mode = self.execute_pi17::<MOD>(()).await?;
+                let ws = self.execute_pi17::<WS>(()).await?;
+                InverterStatus::from((&gs, &mode, &ws))
+            }
+            Some(18) => {
+                let gs = self.execute_pi18::<PI18GS>(()).await?;
+                let mode = self.execute_pi18::<PI18MOD>(()).await?;
+                let fws = self.execute_pi18::<FWS>(()).await?;
+                InverterStatus::from((&gs, &mode, &fws))
+            }
+            _ => {
+                let gs = self.execute::<QPIGS>(()).await?;
+                let mode = self.execute::<QMOD>(()).await?;
+                let ws = self.execute::<QPIWS>(()).await?;
+                InverterStatus::from((&gs, &mode, &ws))
+            }
+        })
+    }
+
+    async fn settings(&mut self) -> Result<InverterSettings> {
+        Ok(match self.capabilities().map(|x| x.protocol_id) {
+            // PI17 has no query for the configurable settings.
+            Some(17) => {
+                return Err(
+                    Error::new(ErrorKind::UnsupportedCommand).with_command("InverterSettings")
+                )
+            }
+            Some(18) => InverterSettings::from(&self.execute_pi18::<PI18PIRI>(()).await?),
+            _ => InverterSettings::from(&self.execute::<QPIRI>(()).await?),
+        })
+    }
+}
+
+#[async_trait]
+impl<S> Backend for Client<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    async fn status(&mut self) -> Result<InverterStatus> {
+        let gs = self.query_general_status().await?;
+        let mode = self.query_device_mode().await?;
+        Ok(InverterStatus::from((&gs, &mode)))
+    }
+
+    async fn settings(&mut self) -> Result<InverterSettings> {
+        Ok(InverterSettings::from(
+            &self.query_rating_information().await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::Backend;
+    use crate::commands::qmod::DeviceMode;
+    use crate::commands::qpiri::BatteryType;
+    use crate::error::{ErrorKind, Result};
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use crate::status::Warning;
+
+    async fn snapshot<B: Backend>(backend: &mut B) -> Result<(DeviceMode, f32)> {
+        let status = backend.status().await?;
+        let settings = backend.settings().await?;
+        Ok((status.mode, settings.battery_bulk_voltage))
+    }
+
+    #[tokio::test]
+    async fn test_backend_pi30() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(
+                b"QPIGS",
+                b"001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0000 074.9 27.12 00005 10110110 17 04 00010 100",
+            )
+            .respond(b"QMOD", b"B")
+            .respond(b"QPIWS", b"00000100000000000000000000000000")
+            .respond(
+                b"QPIRI",
+                b"230.0 13.0 230.0 50.0 13.0 3000 2400 24.0 23.0 21.0 28.2 27.0 0 30 60 0 0 0 - 01 1 0 27.0 0 0",
+            );
+        let mut inverter = Inverter::from_stream(stream);
+
+        let status = inverter.status().await?;
+        assert_eq!(status.battery_current, -5.0);
+        assert_eq!(status.warnings, vec![Warning::LineFail]);
+
+        let settings = inverter.settings().await?;
+        assert_eq!(settings.battery_type, BatteryType::AGM);
+
+        assert_eq!(
+            snapshot(&mut inverter).await?,
+            (DeviceMode::BatteryMode, 28.2)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backend_pi17_settings() -> Result<()> {
+        let stream = MockStream::new().respond(b"^P005PI", b"17");
+        let mut inverter = Inverter::from_stream(stream);
+        inverter.negotiate().await?;
+
+        assert!(matches!(
+            inverter.settings().await.unwrap_err().kind(),
+            ErrorKind::UnsupportedCommand
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+use crate::capabilities::{Capabilities, Pi30Probe};
+use crate::codec::Codec;
+use crate::command::Command;
+use crate::commands::qmn::QMN;
+use crate::commands::qpi::QPI;
+use crate::commands::qpigs2::QPIGS2;
+use crate::commands::qvfw::QVFW;
+use crate::commands::qvfw2::QVFW2;
+use crate::commands::qvfw3::QVFW3;
+use crate::error::{Error, Result};
+use crate::inverter::{check_supported, optional, pi30_protocol_id};
+use crate::pi17;
+use crate::pi17::commands::pi::PI;
+use bytes::{Buf, BytesMut};
+use log::trace;
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Blocking counterpart of `Inverter`, for use without an async runtime.
+///
+/// The timeout bounds a whole transaction. It can only be enforced between reads, so the stream
+/// itself should be given a shorter read timeout (as serial ports usually are); reads that time
+/// out or would block are retried until the deadline passes. A response abandoned this way is
+/// drained from the stream before the next command is sent.
+pub struct SyncInverter<S> {
+    stream: S,
+    buffer_in: BytesMut,
+    capabilities: Option<Capabilities>,
+    timeout: Option<Duration>,
+    command_gap: Duration,
+    last_command: Option<Instant>,
+    timed_out: bool,
+    lenient_decoding: bool,
+}
+
+impl<S> SyncInverter<S> {
+    pub fn from_stream(stream: S) -> Self {
+        Self {
+            stream,
+            buffer_in: BytesMut::new(),
+            capabilities: None,
+            timeout: None,
+            command_gap: Duration::from_secs(0),
+            last_command: None,
+            timed_out: false,
+            lenient_decoding: false,
+        }
+    }
+
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Wait at least `gap` after each response before sending the next command. Some firmware
+    /// drops commands that follow the previous one too closely.
+    pub fn with_command_gap(mut self, gap: Duration) -> Self {
+        self.command_gap = gap;
+        self
+    }
+
+    /// Keep codes unknown to the decoders as `Unknown` enum variants, see
+    /// `Response::decode_lenient`.
+    pub fn with_lenient_decoding(mut self, lenient_decoding: bool) -> Self {
+        self.lenient_decoding = lenient_decoding;
+        self
+    }
+
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    pub fn into_inner(self) -> S {
+        // WARNING: From this point onwards, `buffer_in` is lost! Reading may not be synchronized.
+        self.stream
+    }
+}
+
+impl<S> SyncInverter<S>
+where
+    S: Read + Write,
+{
+    /// Query the protocol id, firmware versions, model name and second PV input and restrict
+    /// further commands to those the device supports.
+    ///
+    /// Some PI17 and PI18 units don't answer `QPI` at all, so detecting them needs a timeout.
+    pub fn negotiate(&mut self) -> Result<&Capabilities> {
+        self.capabilities = None;
+
+        let protocol_id = self.protocol_id()?;
+        let probe = if Pi30Probe::applies_to(protocol_id) {
+            Some(Pi30Probe {
+                firmware_version: optional::<QVFW>(self.execute::<QVFW>(()))?
+                    .map(|x| x.firmware_version),
+                firmware_version2: optional::<QVFW2>(self.execute::<QVFW2>(()))?
+                    .map(|x| x.firmware_version),
+                firmware_version3: optional::<QVFW3>(self.execute::<QVFW3>(()))?
+                    .map(|x| x.firmware_version),
+                model_name: optional::<QMN>(self.execute::<QMN>(()))?.map(|x| x.model_name),
+                dual_mppt: optional::<QPIGS2>(self.execute::<QPIGS2>(()))?.is_some(),
+            })
+        } else {
+            None
+        };
+
+        let capabilities = Capabilities::negotiated(protocol_id, probe);
+        Ok(self.capabilities.get_or_insert(capabilities))
+    }
+
+    // PI17 and PI18 devices don't understand `QPI`, so fall back to their own protocol query.
+    fn protocol_id(&mut self) -> Result<u64> {
+        Ok(match pi30_protocol_id(self.execute::<QPI>(()))? {
+            Some(x) => x,
+            None => self.execute_pi17::<PI>(())?.protocol_id,
+        })
+    }
+
+    pub fn execute<C: Command>(&mut self, req: C::Request) -> Result<C::Response> {
+        check_supported::<C>(self.capabilities.as_ref())?;
+        let codec = Codec::<C>::new().with_lenient_decoding(self.lenient_decoding);
+        self.transact(codec, req)
+    }
+
+    pub fn execute_pi17<C: Command>(&mut self, req: C::Request) -> Result<C::Response> {
+        check_supported::<C>(self.capabilities.as_ref())?;
+        let codec = pi17::codec::Codec::<C>::new().with_lenient_decoding(self.lenient_decoding);
+        self.transact(codec, req)
+    }
+
+    // PI18 shares the PI17 framing.
+    pub fn execute_pi18<C: Command>(&mut self, req: C::Request) -> Result<C::Response> {
+        self.execute_pi17::<C>(req)
+    }
+
+    fn transact<D, I>(&mut self, codec: D, req: I) -> Result<D::Item>
+    where
+        D: Decoder<Error = Error> + Encoder<I, Error = Error>,
+    {
+        if let Some(last_command) = self.last_command {
+            thread::sleep(
+                (last_command + self.command_gap).saturating_duration_since(Instant::now()),
+            );
+        }
+
+        if self.timed_out {
+            self.drain()?;
+            self.timed_out = false;
+        }
+
+        let res = self.exchange(codec, req);
+        self.last_command = Some(Instant::now());
+
+        res
+    }
+
+    fn exchange<D, I>(&mut self, mut codec: D, req: I) -> Result<D::Item>
+    where
+        D: Decoder<Error = Error> + Encoder<I, Error = Error>,
+    {
+        let mut buf = BytesMut::new();
+        codec.encode(req, &mut buf)?;
+
+        trace!("Writing command to stream");
+        self.stream.flush()?;
+        self.stream.write_all(buf.bytes())?;
+
+        let deadline = self.timeout.map(|x| Instant::now() + x);
+        let mut chunk = [0u8; 1024];
+        Ok(loop {
+            if deadline.map_or(false, |x| Instant::now() >= x) {
+                // Whatever arrived so far belongs to the abandoned response, and so does anything
+                // still on its way.
+                self.buffer_in.clear();
+                self.timed_out = true;
+                return Err(io::Error::from(io::ErrorKind::TimedOut).into());
+            }
+
+            let len = match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(len) => len,
+                Err(e) if is_retryable(&e) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            self.buffer_in.extend_from_slice(&chunk[..len]);
+
+            if let Some(item) = codec.decode(&mut self.buffer_in)? {
+                break item;
+            }
+        })
+    }
+
+    // Discard the late reply to a timed out command until the stream goes quiet, so that it isn't
+    // taken for the answer to the next one.
+    fn drain(&mut self) -> Result<()> {
+        let deadline = self.timeout.map(|x| Instant::now() + x);
+        let mut chunk = [0u8; 1024];
+        loop {
+            if deadline.map_or(false, |x| Instant::now() >= x) {
+                break;
+            }
+
+            match self.stream.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(len) => trace!("Discarding {} stale bytes", len),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if is_retryable(&e) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_retryable(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blocking::SyncInverter;
+    use crate::capabilities::Capabilities;
+    use crate::commands::qid::QID;
+    use crate::commands::qmod::{DeviceMode, QMOD};
+    use crate::commands::qvfw2::QVFW2;
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::{frame, MockStream};
+    use crate::pi17::commands::pi::PI;
+    use std::collections::VecDeque;
+    use std::io;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_sync_inverter_execute() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QMOD", b"B")
+            .respond(b"^P005PI", b"17");
+        let mut inverter = SyncInverter::from_stream(stream);
+
+        assert_eq!(inverter.execute::<QMOD>(())?.mode, DeviceMode::BatteryMode);
+        assert_eq!(inverter.execute_pi17::<PI>(())?.protocol_id, 17);
+
+        let err = inverter.execute::<QID>(()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+
+        // A rejected response must not stay in the buffer.
+        assert_eq!(inverter.execute::<QMOD>(())?.mode, DeviceMode::BatteryMode);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_inverter_lenient_decoding() -> Result<()> {
+        let stream = MockStream::new().respond(b"QMOD", b"X");
+        let mut inverter = SyncInverter::from_stream(stream);
+
+        let err = inverter.execute::<QMOD>(()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidDeviceMode));
+
+        let mut inverter = inverter.with_lenient_decoding(true);
+        assert_eq!(
+            inverter.execute::<QMOD>(())?.mode,
+            DeviceMode::Unknown(String::from("X"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_inverter_unsupported() {
+        let mut inverter =
+            SyncInverter::from_stream(MockStream::new()).with_capabilities(Capabilities::new(17));
+
+        let err = inverter.execute::<QMOD>(()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedCommand));
+        assert!(inverter.into_inner().requests.is_empty());
+    }
+
+    const READ_TIMEOUT: Duration = Duration::from_millis(50);
+
+    // Answers each command with the next scripted reply, sending every chunk once its delay after
+    // the command has passed. Reads time out like those of a serial port.
+    struct ScriptedStream {
+        replies: VecDeque<Vec<(Duration, Vec<u8>)>>,
+        pending: VecDeque<(Instant, Vec<u8>)>,
+    }
+
+    impl ScriptedStream {
+        fn new(replies: Vec<Vec<(Duration, Vec<u8>)>>) -> Self {
+            Self {
+                replies: replies.into(),
+                pending: VecDeque::new(),
+            }
+        }
+    }
+
+    impl io::Read for ScriptedStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let deadline = Instant::now() + READ_TIMEOUT;
+            loop {
+                match self.pending.front() {
+                    Some((at, _)) if *at <= Instant::now() => break,
+                    _ if Instant::now() >= deadline => {
+                        return Err(io::Error::from(io::ErrorKind::TimedOut))
+                    }
+                    _ => thread::sleep(Duration::from_millis(1)),
+                }
+            }
+
+            let (_, chunk) = self.pending.pop_front().unwrap();
+            buf[..chunk.len()].copy_from_slice(&chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    impl io::Write for ScriptedStream {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if buf.ends_with(b"\r") {
+                let now = Instant::now();
+                let reply = self.replies.pop_front().unwrap_or_default();
+                self.pending
+                    .extend(reply.into_iter().map(|(delay, chunk)| (now + delay, chunk)));
+            }
+
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sync_inverter_timeout() -> Result<()> {
+        let stream = ScriptedStream::new(vec![
+            vec![(Duration::from_millis(0), b"(B".to_vec())],
+            vec![(Duration::from_millis(0), frame(b"L"))],
+        ]);
+        let mut inverter =
+            SyncInverter::from_stream(stream).with_timeout(Duration::from_millis(100));
+
+        let err = inverter.execute::<QMOD>(()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Io(e) if e.kind() == io::ErrorKind::TimedOut));
+        assert!(inverter.buffer_in.is_empty());
+
+        // The next response is decoded without the leftovers of the abandoned one.
+        assert_eq!(inverter.execute::<QMOD>(())?.mode, DeviceMode::LineMode);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_inverter_late_response() -> Result<()> {
+        let stream = ScriptedStream::new(vec![
+            vec![(Duration::from_millis(130), frame(b"B"))],
+            vec![(Duration::from_millis(0), frame(b"L"))],
+        ]);
+        let mut inverter =
+            SyncInverter::from_stream(stream).with_timeout(Duration::from_millis(100));
+
+        let err = inverter.execute::<QMOD>(()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Io(e) if e.kind() == io::ErrorKind::TimedOut));
+
+        // The late reply to the first command is drained rather than taken for the second.
+        assert_eq!(inverter.execute::<QMOD>(())?.mode, DeviceMode::LineMode);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_inverter_command_gap() -> Result<()> {
+        let gap = Duration::from_millis(20);
+        let stream = MockStream::new().respond(b"QMOD", b"B");
+        let mut inverter = SyncInverter::from_stream(stream).with_command_gap(gap);
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            inverter.execute::<QMOD>(())?;
+        }
+        assert!(start.elapsed() >= gap * 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_inverter_negotiate() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QPI", b"PI30")
+            .respond(b"QVFW", b"VERFW:00072.70")
+            .respond(b"QMN", b"MKS2-5000");
+        let mut inverter = SyncInverter::from_stream(stream);
+
+        let capabilities = inverter.negotiate()?;
+        assert_eq!(capabilities.protocol_id, 30);
+        assert_eq!(capabilities.model_name.as_deref(), Some("MKS2-5000"));
+        assert!(!capabilities.supports::<QVFW2>());
+
+        let err = inverter.execute::<QVFW2>(()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedCommand));
+
+        let mut inverter = SyncInverter::from_stream(MockStream::new().respond(b"^P005PI", b"18"));
+        assert_eq!(inverter.negotiate()?.protocol_id, 18);
+
+        // A device that ignores `QPI` is detected once the query times out.
+        let stream = MockStream::new().ignore(b"QPI").respond(b"^P005PI", b"18");
+        let mut inverter =
+            SyncInverter::from_stream(stream).with_timeout(Duration::from_millis(100));
+        assert_eq!(inverter.negotiate()?.protocol_id, 18);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use crate::commands::bms::{BMSRequest, BMS};
+use crate::error::{ErrorKind, Result};
+use crate::executor::Executor;
+use async_trait::async_trait;
+use log::warn;
+use std::time::Duration;
+use tokio::time;
+
+/// Where BMS readings come from, e.g. the CAN or RS485 link of a lithium pack.
+#[async_trait]
+pub trait BmsSource {
+    async fn read(&mut self) -> Result<BMSRequest>;
+}
+
+/// Pushes readings from a `BmsSource` to the inverter at a fixed rate.
+///
+/// The interval has to stay well within the firmware's BMS timeout, after which it goes back to
+/// its own battery settings. A slow source delays the next update rather than skipping it. Feed
+/// through an `InverterHandle` to keep polling the device from other tasks meanwhile.
+pub struct BmsFeeder<B> {
+    source: B,
+    interval: Duration,
+}
+
+impl<B> BmsFeeder<B>
+where
+    B: BmsSource + Send,
+{
+    pub fn new(source: B, interval: Duration) -> Self {
+        Self { source, interval }
+    }
+
+    pub fn into_inner(self) -> B {
+        self.source
+    }
+
+    /// Send a single reading.
+    pub async fn feed<E>(&mut self, executor: &mut E) -> Result<()>
+    where
+        E: Executor + Send,
+    {
+        let reading = self.source.read().await?;
+        executor.execute::<BMS>(reading).await?;
+
+        Ok(())
+    }
+
+    /// Send a reading every interval, starting right away. Failed updates, such as a NAK or a
+    /// corrupted reply, are logged and the next one is sent as usual. Only returns once the source
+    /// or the link fails with an I/O error.
+    pub async fn run<E>(&mut self, executor: &mut E) -> Result<()>
+    where
+        E: Executor + Send,
+    {
+        let mut interval = time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            match self.feed(executor).await {
+                Ok(()) => {}
+                Err(e) if matches!(e.kind(), ErrorKind::Io(_)) => return Err(e),
+                Err(e) => warn!("BMS update failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bms::{BmsFeeder, BmsSource};
+    use crate::commands::bms::BMSRequest;
+    use crate::commands::qmod::{DeviceMode, QMOD};
+    use crate::error::{ErrorKind, Result};
+    use crate::handle::InverterHandle;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use async_trait::async_trait;
+    use std::collections::VecDeque;
+    use std::io;
+    use std::time::{Duration, Instant};
+
+    struct Readings(VecDeque<BMSRequest>);
+
+    #[async_trait]
+    impl BmsSource for Readings {
+        async fn read(&mut self) -> Result<BMSRequest> {
+            self.0
+                .pop_front()
+                .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof).into())
+        }
+    }
+
+    fn reading(soc: u8) -> BMSRequest {
+        BMSRequest {
+            soc,
+            charge_allowed: true,
+            discharge_allowed: true,
+            max_charge_current: 50,
+            max_discharge_current: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bms_feeder_run() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"BMS08511050100", b"ACK")
+            .respond(b"BMS08411050100", b"ACK");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let interval = Duration::from_millis(20);
+        let source = Readings(vec![reading(85), reading(84), reading(84)].into());
+        let mut feeder = BmsFeeder::new(source, interval);
+
+        let start = Instant::now();
+        let err = feeder.run(&mut inverter).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Io(_)));
+        assert!(start.elapsed() >= interval * 3);
+
+        assert_eq!(
+            inverter.into_inner().requests,
+            vec![
+                b"BMS08511050100".to_vec(),
+                b"BMS08411050100".to_vec(),
+                b"BMS08411050100".to_vec(),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bms_feeder_nak() -> Result<()> {
+        let mut inverter = Inverter::from_stream(MockStream::new());
+        let source = Readings(vec![reading(85), reading(84)].into());
+        let mut feeder = BmsFeeder::new(source, Duration::from_millis(1));
+
+        let err = feeder.feed(&mut inverter).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+
+        // NAKs don't stop the feeder, the source running dry does.
+        let err = feeder.run(&mut inverter).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Io(_)));
+        assert_eq!(inverter.into_inner().requests.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bms_feeder_handle() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"BMS08511050100", b"ACK")
+            .respond(b"BMS08411050100", b"ACK")
+            .respond(b"QMOD", b"B");
+        let handle = InverterHandle::spawn(Inverter::from_stream(stream));
+
+        let source = Readings(vec![reading(85), reading(84)].into());
+        let mut feeder = BmsFeeder::new(source, Duration::from_millis(20));
+        let mut feeder_handle = handle.clone();
+
+        // The device can still be polled while the feeder runs.
+        let (fed, mode) = tokio::join!(feeder.run(&mut feeder_handle), handle.execute::<QMOD>(()));
+        assert!(matches!(fed.unwrap_err().kind(), ErrorKind::Io(_)));
+        assert_eq!(mode?.mode, DeviceMode::BatteryMode);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,391 @@
+use crate::command::Command;
+use crate::commands::bms::BMS;
+use crate::commands::dat::DAT;
+use crate::commands::f::F;
+use crate::commands::pbeqa::PBEQA;
+use crate::commands::pbeqe::PBEQE;
+use crate::commands::pbeqot::PBEQOT;
+use crate::commands::pbeqp::PBEQP;
+use crate::commands::pbeqt::PBEQT;
+use crate::commands::pbeqv::PBEQV;
+use crate::commands::pbt::PBT;
+use crate::commands::pgr::PGR;
+use crate::commands::pledb::PLEDB;
+use crate::commands::pledc::PLEDC;
+use crate::commands::plede::PLEDE;
+use crate::commands::pledm::PLEDM;
+use crate::commands::pleds::PLEDS;
+use crate::commands::pledt::PLEDT;
+use crate::commands::popm::POPM;
+use crate::commands::qbeqi::QBEQI;
+use crate::commands::qboot::QBOOT;
+use crate::commands::qfr::QFR;
+use crate::commands::qgmn::QGMN;
+use crate::commands::qid::QID;
+use crate::commands::qled::QLED;
+#[cfg(feature = "std")]
+use crate::commands::qmn::QMN;
+use crate::commands::qmod::QMOD;
+use crate::commands::qopm::QOPM;
+use crate::commands::qpgs::QPGS;
+use crate::commands::qpi::QPI;
+use crate::commands::qpigs::QPIGS;
+#[cfg(feature = "std")]
+use crate::commands::qpigs2::QPIGS2;
+use crate::commands::qpiri::QPIRI;
+use crate::commands::qpiws::QPIWS;
+use crate::commands::qsid::QSID;
+use crate::commands::qt::QT;
+use crate::commands::qvfw::QVFW;
+#[cfg(feature = "std")]
+use crate::commands::qvfw2::QVFW2;
+#[cfg(feature = "std")]
+use crate::commands::qvfw3::QVFW3;
+use crate::commands::v::V;
+use crate::firmware::FirmwareVersion;
+use crate::pi17::commands::et::ET;
+use crate::pi17::commands::gs::GS;
+use crate::pi17::commands::mode::MOD;
+use crate::pi17::commands::pi::PI;
+use crate::pi17::commands::piri::PIRI;
+use crate::pi17::commands::ws::WS;
+use crate::pi18::commands::et::ET as PI18ET;
+use crate::pi18::commands::fws::FWS;
+use crate::pi18::commands::gs::GS as PI18GS;
+use crate::pi18::commands::id::ID;
+use crate::pi18::commands::mode::MOD as PI18MOD;
+use crate::pi18::commands::pi::PI as PI18PI;
+use crate::pi18::commands::piri::PIRI as PI18PIRI;
+use crate::pi18::commands::vfw::VFW;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use log::debug;
+use serde_derive::Serialize;
+
+const PI30_COMMANDS: &[&str] = &[
+    BMS::COMMAND_NAME,
+    DAT::COMMAND_NAME,
+    F::COMMAND_NAME,
+    PBEQA::COMMAND_NAME,
+    PBEQE::COMMAND_NAME,
+    PBEQOT::COMMAND_NAME,
+    PBEQP::COMMAND_NAME,
+    PBEQT::COMMAND_NAME,
+    PBEQV::COMMAND_NAME,
+    PBT::COMMAND_NAME,
+    PGR::COMMAND_NAME,
+    PLEDB::COMMAND_NAME,
+    PLEDC::COMMAND_NAME,
+    PLEDE::COMMAND_NAME,
+    PLEDM::COMMAND_NAME,
+    PLEDS::COMMAND_NAME,
+    PLEDT::COMMAND_NAME,
+    POPM::COMMAND_NAME,
+    QBEQI::COMMAND_NAME,
+    QBOOT::COMMAND_NAME,
+    QFR::COMMAND_NAME,
+    QGMN::COMMAND_NAME,
+    QID::COMMAND_NAME,
+    QLED::COMMAND_NAME,
+    QMOD::COMMAND_NAME,
+    QOPM::COMMAND_NAME,
+    QPGS::COMMAND_NAME,
+    QPI::COMMAND_NAME,
+    QPIGS::COMMAND_NAME,
+    QPIRI::COMMAND_NAME,
+    QPIWS::COMMAND_NAME,
+    QSID::COMMAND_NAME,
+    QT::COMMAND_NAME,
+    QVFW::COMMAND_NAME,
+    V::COMMAND_NAME,
+];
+
+const PI17_COMMANDS: &[&str] = &[
+    ET::COMMAND_NAME,
+    GS::COMMAND_NAME,
+    MOD::COMMAND_NAME,
+    PI::COMMAND_NAME,
+    PIRI::COMMAND_NAME,
+    WS::COMMAND_NAME,
+];
+
+const PI18_COMMANDS: &[&str] = &[
+    PI18ET::COMMAND_NAME,
+    FWS::COMMAND_NAME,
+    ID::COMMAND_NAME,
+    PI18MOD::COMMAND_NAME,
+    PI18PI::COMMAND_NAME,
+    PI18GS::COMMAND_NAME,
+    PI18PIRI::COMMAND_NAME,
+    VFW::COMMAND_NAME,
+];
+
+/// What a device has been detected to support, built by `Inverter::negotiate`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Capabilities {
+    pub protocol_id: u64,
+    pub firmware_version: Option<FirmwareVersion>,
+    pub firmware_version2: Option<FirmwareVersion>,
+    // Remote panel firmware.
+    pub firmware_version3: Option<FirmwareVersion>,
+    pub model_name: Option<String>,
+    pub commands: BTreeSet<&'static str>,
+}
+
+impl Capabilities {
+    /// Capabilities assumed for a protocol before probing for the optional queries.
+    pub fn new(protocol_id: u64) -> Self {
+        let mut commands = BTreeSet::new();
+        match protocol_id {
+            17 => commands.extend(PI17_COMMANDS),
+            18 => commands.extend(PI18_COMMANDS),
+            30 => commands.extend(PI30_COMMANDS),
+            _ => {
+                commands.insert(QPI::COMMAND_NAME);
+            }
+        }
+
+        Self {
+            protocol_id,
+            firmware_version: None,
+            firmware_version2: None,
+            firmware_version3: None,
+            model_name: None,
+            commands,
+        }
+    }
+
+    pub fn supports<C: Command>(&self) -> bool {
+        self.commands.contains(C::COMMAND_NAME)
+    }
+
+    /// Capabilities found by `negotiate`, from the protocol id and the answers to the optional
+    /// PI30 queries, which are only made for PI30 devices.
+    #[cfg(feature = "std")]
+    pub(crate) fn negotiated(protocol_id: u64, probe: Option<Pi30Probe>) -> Self {
+        let mut capabilities = Self::new(protocol_id);
+        if let Some(probe) = probe {
+            capabilities.set_supported::<QVFW>(probe.firmware_version.is_some());
+            capabilities.set_supported::<QVFW2>(probe.firmware_version2.is_some());
+            capabilities.set_supported::<QVFW3>(probe.firmware_version3.is_some());
+            capabilities.set_supported::<QMN>(probe.model_name.is_some());
+            capabilities.set_supported::<QPIGS2>(probe.dual_mppt);
+
+            capabilities.firmware_version = probe.firmware_version;
+            capabilities.firmware_version2 = probe.firmware_version2;
+            capabilities.firmware_version3 = probe.firmware_version3;
+            capabilities.model_name = probe.model_name;
+        }
+
+        debug!("Negotiated capabilities: {:?}", capabilities);
+        capabilities
+    }
+
+    #[cfg(feature = "std")]
+    fn set_supported<C: Command>(&mut self, supported: bool) {
+        if supported {
+            self.commands.insert(C::COMMAND_NAME);
+        } else {
+            self.commands.remove(C::COMMAND_NAME);
+        }
+    }
+}
+
+/// Answers to the optional queries `negotiate` makes on PI30 devices, `None` (or `false`) for those
+/// the device rejected.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub(crate) struct Pi30Probe {
+    pub(crate) firmware_version: Option<FirmwareVersion>,
+    pub(crate) firmware_version2: Option<FirmwareVersion>,
+    pub(crate) firmware_version3: Option<FirmwareVersion>,
+    pub(crate) model_name: Option<String>,
+    pub(crate) dual_mppt: bool,
+}
+
+#[cfg(feature = "std")]
+impl Pi30Probe {
+    pub(crate) fn applies_to(protocol_id: u64) -> bool {
+        protocol_id == 30
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::capabilities::{Capabilities, Pi30Probe};
+    use crate::commands::qid::QID;
+    use crate::commands::qmn::QMN;
+    use crate::commands::qmod::DeviceMode;
+    use crate::commands::qpi::QPI;
+    use crate::commands::qpigs::QPIGS;
+    use crate::commands::qpigs2::QPIGS2;
+    use crate::commands::qvfw2::QVFW2;
+    use crate::commands::qvfw3::QVFW3;
+    use crate::error::{ErrorKind, Result};
+    use crate::firmware::FirmwareVersion;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use crate::pi17::commands::gs::GS;
+    use crate::pi17::commands::mode::MOD;
+    use crate::pi18::commands::et::ET as PI18ET;
+    use crate::pi18::commands::gs::GS as PI18GS;
+    use crate::pi18::commands::mode::MOD as PI18MOD;
+    use std::time::Duration;
+
+    #[test]
+    fn test_capabilities_pi30() {
+        let capabilities = Capabilities::new(30);
+
+        assert!(capabilities.supports::<QPI>());
+        assert!(capabilities.supports::<QPIGS>());
+        assert!(!capabilities.supports::<QVFW2>());
+        assert!(!capabilities.supports::<QMN>());
+    }
+
+    #[test]
+    fn test_capabilities_pi17() {
+        let capabilities = Capabilities::new(17);
+
+        assert!(capabilities.supports::<GS>());
+        assert!(!capabilities.supports::<QPI>());
+        assert!(!capabilities.supports::<QPIGS>());
+    }
+
+    #[test]
+    fn test_capabilities_unknown_protocol() {
+        let capabilities = Capabilities::new(16);
+
+        assert!(capabilities.supports::<QPI>());
+        assert!(!capabilities.supports::<QPIGS>());
+    }
+
+    #[test]
+    fn test_capabilities_negotiated() {
+        let probe = Pi30Probe {
+            firmware_version: Some(FirmwareVersion::new(0x72, 0x70)),
+            dual_mppt: true,
+            ..Pi30Probe::default()
+        };
+        let capabilities = Capabilities::negotiated(30, Some(probe));
+
+        assert!(capabilities.supports::<QPIGS2>());
+        assert!(!capabilities.supports::<QVFW2>());
+        assert!(!capabilities.supports::<QMN>());
+        assert_eq!(capabilities.model_name, None);
+
+        // Other protocols keep their defaults.
+        assert_eq!(Capabilities::negotiated(17, None), Capabilities::new(17));
+    }
+
+    #[tokio::test]
+    async fn test_negotiate() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QPI", b"PI30")
+            .respond(b"QVFW", b"VERFW:00072.70")
+            .respond(b"QVFW3", b"VERFW3:00001.0A")
+            .respond(b"QMN", b"MKS2-5000");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let capabilities = inverter.negotiate().await?.clone();
+        assert_eq!(capabilities.protocol_id, 30);
+        assert_eq!(
+            capabilities.firmware_version,
+            Some(FirmwareVersion::new(0x72, 0x70))
+        );
+        assert_eq!(capabilities.firmware_version2, None);
+        assert_eq!(
+            capabilities
+                .firmware_version3
+                .map(|x| x.to_string())
+                .as_deref(),
+            Some("00001.0A")
+        );
+        assert_eq!(capabilities.model_name.as_deref(), Some("MKS2-5000"));
+        assert!(capabilities.supports::<QMN>());
+        assert!(!capabilities.supports::<QVFW2>());
+        assert!(capabilities.supports::<QVFW3>());
+        assert!(!capabilities.supports::<QPIGS2>());
+
+        // Unsupported commands are rejected without touching the stream.
+        let err = inverter.execute::<QVFW2>(()).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedCommand));
+        assert_eq!(err.command(), Some("QueryFirmwareVersion2"));
+        assert_eq!(inverter.into_inner().requests.len(), 6);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_pi17() -> Result<()> {
+        let stream = MockStream::new().respond(b"^P005PI", b"17");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let capabilities = inverter.negotiate().await?;
+        assert_eq!(capabilities.protocol_id, 17);
+        assert!(capabilities.supports::<GS>());
+
+        let err = inverter.execute::<QPIGS>(()).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedCommand));
+        assert_eq!(err.command(), Some("QueryDeviceGeneralStatus"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_pi17_silent() -> Result<()> {
+        // The device ignores the unknown PI30 frame instead of rejecting it.
+        let stream = MockStream::new().ignore(b"QPI").respond(b"^P005PI", b"17");
+        let mut inverter = Inverter::from_stream(stream).with_timeout(Duration::from_millis(100));
+
+        let capabilities = inverter.negotiate().await?;
+        assert_eq!(capabilities.protocol_id, 17);
+        assert_eq!(
+            inverter.into_inner().requests,
+            vec![b"QPI".to_vec(), b"^P005PI".to_vec()]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_pi18() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"^P005PI", b"18")
+            .respond(b"^P006MOD", b"03");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let capabilities = inverter.negotiate().await?;
+        assert_eq!(capabilities.protocol_id, 18);
+        assert!(capabilities.supports::<PI18GS>());
+        assert!(!capabilities.supports::<GS>());
+        assert!(!capabilities.supports::<MOD>());
+
+        let item = inverter.execute_pi18::<PI18MOD>(()).await?;
+        assert_eq!(item.mode, DeviceMode::BatteryMode);
+
+        // Errors name the PI18 command.
+        let err = inverter.execute_pi17::<MOD>(()).await.unwrap_err();
+        assert_eq!(err.command(), Some("PI17QueryWorkingMode"));
+        let err = inverter.execute_pi18::<PI18ET>(()).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+        assert_eq!(err.command(), Some("PI18QueryTotalGeneratedEnergy"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_other_protocol() -> Result<()> {
+        let stream = MockStream::new().respond(b"QPI", b"PI16");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let capabilities = inverter.negotiate().await?;
+        assert_eq!(capabilities.protocol_id, 16);
+
+        let err = inverter.execute::<QID>(()).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::UnsupportedCommand));
+        assert_eq!(err.command(), Some("QuerySerialNumber"));
+
+        Ok(())
+    }
+}
@@ -1,27 +1,28 @@
-use crate::command::{Command, Request, Response};
+use crate::command::Command;
 use crate::error::{Error, Result};
+use crate::frame;
 use bytes::{Buf, BufMut, BytesMut};
-use crc_any::CRCu16;
 use log::{debug, trace};
 use std::marker::PhantomData;
 use tokio_util::codec::{Decoder, Encoder};
 
 pub struct Codec<C> {
+    lenient_decoding: bool,
     phantom: PhantomData<C>,
 }
 
 impl<C> Codec<C> {
     pub fn new() -> Self {
         Self {
-            phantom: PhantomData::default(),
+            lenient_decoding: false,
+            phantom: PhantomData,
         }
     }
 
-    fn compute_crc(data: &[u8]) -> u16 {
-        let mut computed_crc_sum = CRCu16::crc16xmodem();
-        computed_crc_sum.digest(data);
-
-        computed_crc_sum.get_crc()
+    /// Decode responses with `Response::decode_lenient`.
+    pub fn with_lenient_decoding(mut self, lenient_decoding: bool) -> Self {
+        self.lenient_decoding = lenient_decoding;
+        self
     }
 }
 
@@ -39,85 +40,28 @@ where
     type Error = Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
-        let maybe_index = src
-            .iter()
-            .copied()
-            .enumerate()
-            .find(|(_, x)| *x == b'\r')
-            .map(|(i, _)| i);
-
-        Ok(match maybe_index {
-            Some(index) => {
-                let mut item = &src[..index];
-                let mut recover_length = 0;
-                if *item.first().unwrap() != b'(' {
-                    trace!(
-                        "Invalid response format ({}): {:?}",
-                        C::COMMAND_NAME,
-                        &item[..]
-                    );
-
-                    // Try to recover by removing everything until the first (
-                    let index = item.iter().position(|&r| r == b'(');
-
-                    if index.is_none() {
-                        src.advance(src.len());
-                        return Err(Error::InvalidResponseFormat);
-                    }
-
-                    trace!("Attempting to recover from invalid response");
-                    let split = item.split_at(index.unwrap());
-                    recover_length = split.0.len();
-                    item = split.1;
-                }
-
-                debug!("Decoding response {}.", C::COMMAND_NAME);
-                trace!("Decoding response ({}): {:?}.", C::COMMAND_NAME, &item[..]);
-
-                for step in 0..3 {
-                    // Check the CRC for the current detected payload.
-                    let crc_sum = Self::compute_crc(&item[..item.len() - 2]);
-                    if crc_sum == (&item[item.len() - 2..]).get_u16() {
-                        break;
-                    }
-
-                    // Check src length.
-                    // Ensure the new command response ends in \r.
-                    if index + step + 1 == src.len()
-                        || !match step {
-                            0 => {
-                                let check = src[index + 1] == b'\r';
-                                if !check
-                                    && (index + step + 2 < src.len() && src[index + 2] == b'\r')
-                                {
-                                    continue;
-                                }
-
-                                check
-                            }
-                            1 => src[index + 2] == b'\r',
-                            _ => unimplemented!(),
-                        }
-                    {
-                        src.advance(src.len());
-                        return Err(Error::InvalidResponseCrcSum);
-                    }
-
-                    item = &src[..index + step + 1];
-                }
-
-                // TODO: Do this without copying memory.
-                let decoded_item =
-                    C::Response::decode(&mut BytesMut::from(&item[1..item.len() - 2]))?;
-                trace!("Decoded response ({}): {:?}", C::COMMAND_NAME, decoded_item);
-
-                let item_len = item.len();
-                src.advance(item_len + recover_length + 1);
-
-                Some(decoded_item)
-            }
-            None => None,
-        })
+        let item = match frame::decode_pi30(src) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        debug!("Decoding response {}.", C::COMMAND_NAME);
+        trace!("Decoding response ({}): {:?}.", C::COMMAND_NAME, item.raw);
+
+        // Consume the frame before decoding so that a rejected or malformed response doesn't
+        // stay in the buffer and poison the next command.
+        let consumed = item.consumed;
+        let res = if self.lenient_decoding {
+            item.decode_lenient::<C>()
+        } else {
+            item.decode::<C>()
+        };
+        src.advance(consumed);
+
+        let decoded_item = res?;
+        trace!("Decoded response ({}): {:?}", C::COMMAND_NAME, decoded_item);
+
+        Ok(Some(decoded_item))
     }
 }
 
@@ -128,35 +72,12 @@ where
     type Error = Error;
 
     fn encode(&mut self, item: C::Request, dst: &mut BytesMut) -> Result<()> {
-        // Note: Used for logging.
-        let start_len = dst.len();
-
         debug!("Encoding command {}.", C::COMMAND_NAME);
         trace!("Command payload ({}): {:?}.", C::COMMAND_NAME, item);
 
-        // Put the command id.
-        dst.put_slice(C::PROTOCOL_ID);
-
-        // Put the request payload (if any).
-        if let Some(request_payload) = item.encode()? {
-            dst.put(request_payload);
-        }
-
-        // Compute the CRC sum.
-        let mut crc_sum = CRCu16::crc16xmodem();
-        crc_sum.digest(dst.bytes());
-        let crc_sum = crc_sum.get_crc();
-
-        // Put the CRC sum.
-        // Put the carriage return.
-        dst.put_u16(crc_sum);
-        dst.put_u8(b'\r');
-
-        trace!(
-            "Encoded command ({}): {:?}",
-            C::COMMAND_NAME,
-            &dst[start_len..]
-        );
+        let encoded = frame::encode_pi30::<C>(&item)?;
+        trace!("Encoded command ({}): {:?}", C::COMMAND_NAME, encoded);
+        dst.put_slice(&encoded);
 
         Ok(())
     }
@@ -165,8 +86,12 @@ where
 #[cfg(test)]
 mod test {
     use crate::codec::Codec;
+    use crate::command::Ack;
+    use crate::commands::pbt::PBT;
     use crate::commands::qid::{QIDResponse, QID};
-    use crate::error::Result;
+    use crate::commands::qmod::{DeviceMode, QMODResponse, QMOD};
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
     use bytes::BytesMut;
     use crc_any::CRCu16;
     use tokio_util::codec::Decoder;
@@ -207,10 +132,85 @@ mod test {
         assert_eq!(
             item.unwrap(),
             QIDResponse {
-                serial_number: 12345
+                serial_number: String::from("12345")
             }
         );
 
         Ok(())
     }
+
+    #[test]
+    fn test_decode_empty_ack() {
+        let mut codec = Codec::<PBT>::new();
+
+        // Only the PI17 framing has setter replies without a payload.
+        let mut buf = BytesMut::from(frame(b"").as_slice());
+        let err = codec.decode(&mut buf).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(Ack));
+    }
+
+    #[test]
+    fn test_decode_nak() -> Result<()> {
+        let mut codec = Codec::<QID>::new();
+
+        let mut res = String::from("(NAK").into_bytes();
+        let mut crc_sum = CRCu16::crc16xmodem();
+        crc_sum.digest(res.as_slice());
+        res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+        res.push(b'\r');
+
+        let mut buf = BytesMut::from(res.as_slice());
+        let err = codec.decode(&mut buf).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+        assert_eq!(err.command(), Some("QuerySerialNumber"));
+        assert_eq!(err.frame(), Some(&res[..]));
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_invalid_field() {
+        let mut codec = Codec::<QMOD>::new();
+
+        let mut res = String::from("(X").into_bytes();
+        let mut crc_sum = CRCu16::crc16xmodem();
+        crc_sum.digest(res.as_slice());
+        res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+        res.push(b'\r');
+
+        let mut buf = BytesMut::from(res.as_slice());
+        let err = codec.decode(&mut buf).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::InvalidDeviceMode));
+        assert_eq!(err.command(), Some("DeviceModeInquiry"));
+        assert_eq!(err.field(), Some("mode"));
+        assert_eq!(err.raw(), Some(&b"X"[..]));
+        assert_eq!(err.frame(), Some(&res[..]));
+    }
+
+    #[test]
+    fn test_decode_lenient() -> Result<()> {
+        let mut codec = Codec::<QMOD>::new().with_lenient_decoding(true);
+
+        let mut res = String::from("(X").into_bytes();
+        let mut crc_sum = CRCu16::crc16xmodem();
+        crc_sum.digest(res.as_slice());
+        res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+        res.push(b'\r');
+
+        let mut buf = BytesMut::from(res.as_slice());
+        assert_eq!(
+            codec.decode(&mut buf)?,
+            Some(QMODResponse {
+                mode: DeviceMode::Unknown(String::from("X"))
+            })
+        );
+
+        Ok(())
+    }
 }
@@ -1,6 +1,7 @@
-use crate::error::Result;
-use bytes::BytesMut;
-use std::fmt::Debug;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use serde_derive::Serialize;
 
 pub trait Command {
     const PROTOCOL_ID: &'static [u8];
@@ -11,7 +12,7 @@ pub trait Command {
 }
 
 pub trait Request {
-    fn encode(&self) -> Result<Option<BytesMut>> {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
         Ok(None)
     }
 }
@@ -22,5 +23,28 @@ pub trait Response
 where
     Self: Sized,
 {
-    fn decode(src: &mut BytesMut) -> Result<Self>;
+    fn decode(src: &[u8]) -> Result<Self>;
+
+    /// Like `decode`, but codes the decoder doesn't know, e.g. ones added by newer firmware, are
+    /// kept as `Unknown` enum variants instead of failing the whole response.
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode(src)
+    }
+}
+
+/// Reply to a setter that was accepted.
+///
+/// PI30 devices answer `ACK`, PI17 and PI18 devices a bare `^1` frame, which their codec passes
+/// on as `ACK`. Rejected settings come back as `NAK` (or `^0`) and are reported as
+/// `ErrorKind::NotAcknowledged` by the codecs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Ack;
+
+impl Response for Ack {
+    fn decode(src: &[u8]) -> Result<Self> {
+        match src {
+            b"ACK" => Ok(Ack),
+            _ => Err(Error::invalid_field(ErrorKind::InvalidPayload, "ack", src)),
+        }
+    }
 }
@@ -1,8 +1,39 @@
+pub mod bms;
+pub mod dat;
+pub mod f;
+pub mod pbeqa;
+pub mod pbeqe;
+pub mod pbeqot;
+pub mod pbeqp;
+pub mod pbeqt;
+pub mod pbeqv;
+pub mod pbt;
+pub mod pgr;
+pub mod pledb;
+pub mod pledc;
+pub mod plede;
+pub mod pledm;
+pub mod pleds;
+pub mod pledt;
+pub mod popm;
+pub mod qbeqi;
+pub mod qboot;
+pub mod qfr;
+pub mod qgmn;
 pub mod qid;
+pub mod qled;
+pub mod qmn;
 pub mod qmod;
+pub mod qopm;
+pub mod qpgs;
 pub mod qpi;
 pub mod qpigs;
+pub mod qpigs2;
 pub mod qpiri;
 pub mod qpiws;
+pub mod qsid;
+pub mod qt;
 pub mod qvfw;
 pub mod qvfw2;
+pub mod qvfw3;
+pub mod v;
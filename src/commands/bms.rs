@@ -0,0 +1,149 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Passes the state of an external BMS to firmware with "BMS communication via RS232" enabled.
+// The inverter falls back to its own battery settings if it stops receiving updates.
+pub struct BMS;
+
+impl Command for BMS {
+    const PROTOCOL_ID: &'static [u8] = b"BMS";
+    const COMMAND_NAME: &'static str = "SetBmsStatus";
+
+    type Request = BMSRequest;
+    type Response = Ack;
+}
+
+// Sent as `sssCDccciii`: state of charge, charge and discharge flags, then both limits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BMSRequest {
+    // Percent.
+    pub soc: u8,
+    pub charge_allowed: bool,
+    pub discharge_allowed: bool,
+    // Amps, up to 999.
+    pub max_charge_current: u16,
+    pub max_discharge_current: u16,
+}
+
+impl Request for BMSRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        if self.soc > 100 {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "soc",
+                self.soc.to_string().as_bytes(),
+            ));
+        }
+        check_current("max_charge_current", self.max_charge_current)?;
+        check_current("max_discharge_current", self.max_discharge_current)?;
+
+        Ok(Some(
+            format!(
+                "{:03}{}{}{:03}{:03}",
+                self.soc,
+                self.charge_allowed as u8,
+                self.discharge_allowed as u8,
+                self.max_charge_current,
+                self.max_discharge_current
+            )
+            .into_bytes(),
+        ))
+    }
+}
+
+fn check_current(field: &'static str, current: u16) -> Result<()> {
+    if current > 999 {
+        return Err(Error::invalid_field(
+            ErrorKind::InvalidPayload,
+            field,
+            current.to_string().as_bytes(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::bms::{BMSRequest, BMS};
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    fn request() -> BMSRequest {
+        BMSRequest {
+            soc: 85,
+            charge_allowed: true,
+            discharge_allowed: false,
+            max_charge_current: 50,
+            max_discharge_current: 80,
+        }
+    }
+
+    #[test]
+    fn test_bms_payload_encode() -> Result<()> {
+        assert_eq!(request().encode()?, Some(b"08510050080".to_vec()));
+
+        let req = BMSRequest {
+            soc: 100,
+            discharge_allowed: true,
+            max_charge_current: 0,
+            max_discharge_current: 999,
+            ..request()
+        };
+        assert_eq!(req.encode()?, Some(b"10011000999".to_vec()));
+
+        assert!(BMSRequest {
+            soc: 101,
+            ..request()
+        }
+        .encode()
+        .is_err());
+        assert!(BMSRequest {
+            max_charge_current: 1000,
+            ..request()
+        }
+        .encode()
+        .is_err());
+        assert!(BMSRequest {
+            max_discharge_current: 1000,
+            ..request()
+        }
+        .encode()
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bms_command_encode() -> Result<()> {
+        let mut codec = Codec::<BMS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(request(), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"BMS08510050080\xb3\x7b\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bms_command_decode() -> Result<()> {
+        let mut codec = Codec::<BMS>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        let mut buf = BytesMut::from(frame(b"NAK").as_slice());
+        let err = codec.decode(&mut buf).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use crate::command::{Ack, Command, Request};
+use crate::datetime::DateTime;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Date and time setter. The device only takes a two digit year.
+pub struct DAT;
+
+impl Command for DAT {
+    const PROTOCOL_ID: &'static [u8] = b"DAT";
+    const COMMAND_NAME: &'static str = "SetDateTime";
+
+    type Request = DATRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct DATRequest {
+    pub datetime: DateTime,
+}
+
+impl Request for DATRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let x = &self.datetime;
+        if !(2000..=2099).contains(&x.year) || !x.is_valid() {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "datetime",
+                x.to_string().as_bytes(),
+            ));
+        }
+
+        Ok(Some(
+            format!(
+                "{:02}{:02}{:02}{:02}{:02}{:02}",
+                x.year - 2000,
+                x.month,
+                x.day,
+                x.hour,
+                x.minute,
+                x.second
+            )
+            .into_bytes(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::dat::{DATRequest, DAT};
+    use crate::datetime::DateTime;
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    fn datetime(year: u16) -> DateTime {
+        DateTime {
+            year,
+            month: 10,
+            day: 18,
+            hour: 12,
+            minute: 30,
+            second: 0,
+        }
+    }
+
+    #[test]
+    fn test_dat_payload_encode() -> Result<()> {
+        let req = DATRequest {
+            datetime: datetime(2020),
+        };
+        assert_eq!(req.encode()?, Some(b"201018123000".to_vec()));
+
+        let req = DATRequest {
+            datetime: datetime(2100),
+        };
+        assert!(req.encode().is_err());
+
+        for invalid in &[
+            DateTime {
+                month: 13,
+                ..datetime(2020)
+            },
+            DateTime {
+                day: 31,
+                month: 11,
+                ..datetime(2020)
+            },
+            DateTime {
+                hour: 25,
+                ..datetime(2020)
+            },
+            DateTime {
+                minute: 60,
+                ..datetime(2020)
+            },
+            DateTime {
+                second: 60,
+                ..datetime(2020)
+            },
+        ] {
+            let req = DATRequest { datetime: *invalid };
+            assert!(req.encode().is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dat_command_encode() -> Result<()> {
+        let mut codec = Codec::<DAT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            DATRequest {
+                datetime: datetime(2020),
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"DAT201018123000\x96\x15\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dat_command_decode() -> Result<()> {
+        let mut codec = Codec::<DAT>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+        assert_eq!(buf.remaining(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::Result;
+use alloc::format;
+use alloc::vec::Vec;
+use serde_derive::Serialize;
+
+// Sets the AC output frequency.
+pub struct F;
+
+impl Command for F {
+    const PROTOCOL_ID: &'static [u8] = b"F";
+    const COMMAND_NAME: &'static str = "SetOutputFrequency";
+
+    type Request = FRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct FRequest {
+    pub frequency: OutputFrequency,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum OutputFrequency {
+    Hz50,
+    Hz60,
+}
+
+impl OutputFrequency {
+    pub fn from_hertz(hertz: u8) -> Option<Self> {
+        Some(match hertz {
+            50 => Self::Hz50,
+            60 => Self::Hz60,
+            _ => return None,
+        })
+    }
+
+    pub fn hertz(&self) -> u8 {
+        match self {
+            Self::Hz50 => 50,
+            Self::Hz60 => 60,
+        }
+    }
+}
+
+impl Request for FRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(format!("{}", self.frequency.hertz()).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::f::{FRequest, OutputFrequency, F};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_f_payload_encode() -> Result<()> {
+        let encode = |frequency| FRequest { frequency }.encode();
+
+        assert_eq!(encode(OutputFrequency::Hz50)?, Some(b"50".to_vec()));
+        assert_eq!(encode(OutputFrequency::Hz60)?, Some(b"60".to_vec()));
+
+        assert_eq!(OutputFrequency::from_hertz(60), Some(OutputFrequency::Hz60));
+        assert_eq!(OutputFrequency::from_hertz(55), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_f_command_encode() -> Result<()> {
+        let mut codec = Codec::<F>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            FRequest {
+                frequency: OutputFrequency::Hz50,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"F50\x63\x3e\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_f_command_decode() -> Result<()> {
+        let mut codec = Codec::<F>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::Result;
+use alloc::vec::Vec;
+
+// Starts or stops an equalization right away.
+pub struct PBEQA;
+
+impl Command for PBEQA {
+    const PROTOCOL_ID: &'static [u8] = b"PBEQA";
+    const COMMAND_NAME: &'static str = "SetBatteryEqualizationActive";
+
+    type Request = PBEQARequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PBEQARequest {
+    pub active: bool,
+}
+
+impl Request for PBEQARequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(if self.active { b"1" } else { b"0" }.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbeqa::{PBEQARequest, PBEQA};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pbeqa_payload_encode() -> Result<()> {
+        assert_eq!(PBEQARequest { active: true }.encode()?, Some(b"1".to_vec()));
+        assert_eq!(
+            PBEQARequest { active: false }.encode()?,
+            Some(b"0".to_vec())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqa_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBEQA>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PBEQARequest { active: true }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PBEQA1\x86\xd7\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqa_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBEQA>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::Result;
+use alloc::vec::Vec;
+
+// Enables or disables battery equalization.
+pub struct PBEQE;
+
+impl Command for PBEQE {
+    const PROTOCOL_ID: &'static [u8] = b"PBEQE";
+    const COMMAND_NAME: &'static str = "SetBatteryEqualizationEnabled";
+
+    type Request = PBEQERequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PBEQERequest {
+    pub enabled: bool,
+}
+
+impl Request for PBEQERequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(if self.enabled { b"1" } else { b"0" }.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbeqe::{PBEQERequest, PBEQE};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pbeqe_payload_encode() -> Result<()> {
+        assert_eq!(
+            PBEQERequest { enabled: true }.encode()?,
+            Some(b"1".to_vec())
+        );
+        assert_eq!(
+            PBEQERequest { enabled: false }.encode()?,
+            Some(b"0".to_vec())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqe_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBEQE>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PBEQERequest { enabled: true }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PBEQE1\x4a\x13\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqe_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBEQE>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::pbeqt::encode_minutes;
+use crate::error::Result;
+use alloc::vec::Vec;
+
+// Sets how long an equalization may be extended when the voltage isn't reached in time.
+pub struct PBEQOT;
+
+impl Command for PBEQOT {
+    const PROTOCOL_ID: &'static [u8] = b"PBEQOT";
+    const COMMAND_NAME: &'static str = "SetBatteryEqualizationOverTime";
+
+    type Request = PBEQOTRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PBEQOTRequest {
+    // Minutes.
+    pub over_time: u16,
+}
+
+impl Request for PBEQOTRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        encode_minutes(self.over_time, "over_time")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbeqot::{PBEQOTRequest, PBEQOT};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pbeqot_payload_encode() -> Result<()> {
+        assert_eq!(
+            PBEQOTRequest { over_time: 120 }.encode()?,
+            Some(b"120".to_vec())
+        );
+
+        let err = PBEQOTRequest { over_time: 1000 }.encode().unwrap_err();
+        assert_eq!(err.field(), Some("over_time"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqot_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBEQOT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PBEQOTRequest { over_time: 120 }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PBEQOT120\x88\x44\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqot_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBEQOT>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
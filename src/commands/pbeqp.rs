@@ -0,0 +1,78 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Sets the number of days between equalizations.
+pub struct PBEQP;
+
+impl Command for PBEQP {
+    const PROTOCOL_ID: &'static [u8] = b"PBEQP";
+    const COMMAND_NAME: &'static str = "SetBatteryEqualizationPeriod";
+
+    type Request = PBEQPRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PBEQPRequest {
+    // Days, up to 90.
+    pub period: u16,
+}
+
+impl Request for PBEQPRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        if self.period > 90 {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "period",
+                self.period.to_string().as_bytes(),
+            ));
+        }
+
+        Ok(Some(format!("{:03}", self.period).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbeqp::{PBEQPRequest, PBEQP};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pbeqp_payload_encode() -> Result<()> {
+        assert_eq!(PBEQPRequest { period: 0 }.encode()?, Some(b"000".to_vec()));
+        assert_eq!(PBEQPRequest { period: 90 }.encode()?, Some(b"090".to_vec()));
+        assert!(PBEQPRequest { period: 91 }.encode().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqp_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBEQP>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PBEQPRequest { period: 30 }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PBEQP030\x36\x12\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqp_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBEQP>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
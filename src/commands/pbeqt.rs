@@ -0,0 +1,86 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Sets the equalization time.
+pub struct PBEQT;
+
+impl Command for PBEQT {
+    const PROTOCOL_ID: &'static [u8] = b"PBEQT";
+    const COMMAND_NAME: &'static str = "SetBatteryEqualizationTime";
+
+    type Request = PBEQTRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PBEQTRequest {
+    // Minutes.
+    pub time: u16,
+}
+
+impl Request for PBEQTRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        encode_minutes(self.time, "time")
+    }
+}
+
+/// Equalization times are set in steps of 5 minutes, from 5 to 900.
+pub(crate) fn encode_minutes(minutes: u16, field: &'static str) -> Result<Option<Vec<u8>>> {
+    if !(5..=900).contains(&minutes) || minutes % 5 != 0 {
+        return Err(Error::invalid_field(
+            ErrorKind::InvalidPayload,
+            field,
+            minutes.to_string().as_bytes(),
+        ));
+    }
+
+    Ok(Some(format!("{:03}", minutes).into_bytes()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbeqt::{PBEQTRequest, PBEQT};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pbeqt_payload_encode() -> Result<()> {
+        assert_eq!(PBEQTRequest { time: 5 }.encode()?, Some(b"005".to_vec()));
+        assert_eq!(PBEQTRequest { time: 900 }.encode()?, Some(b"900".to_vec()));
+
+        for &time in &[0, 3, 62, 905] {
+            assert!(PBEQTRequest { time }.encode().is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqt_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBEQT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PBEQTRequest { time: 60 }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PBEQT060\x03\x16\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqt_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBEQT>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
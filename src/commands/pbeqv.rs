@@ -0,0 +1,123 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qpiri::QPIRIResponse;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Sets the equalization voltage.
+pub struct PBEQV;
+
+impl Command for PBEQV {
+    const PROTOCOL_ID: &'static [u8] = b"PBEQV";
+    const COMMAND_NAME: &'static str = "SetBatteryEqualizationVoltage";
+
+    type Request = PBEQVRequest;
+    type Response = Ack;
+}
+
+/// The accepted range depends on the battery bank: 12.00 to 15.25 V for each 12 V block, e.g.
+/// 48.00 to 61.00 V on a 48 V unit.
+#[derive(Debug)]
+pub struct PBEQVRequest {
+    pub voltage: f32,
+    // As reported by `QPIRI`.
+    pub battery_rating_voltage: f32,
+}
+
+impl PBEQVRequest {
+    pub fn new(voltage: f32, rating: &QPIRIResponse) -> Self {
+        Self {
+            voltage,
+            battery_rating_voltage: rating.battery_rating_voltage,
+        }
+    }
+}
+
+impl Request for PBEQVRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        // Tenths and hundredths of a volt, rounded without `f32::round` so this works without std.
+        let rating = (self.battery_rating_voltage * 10.0 + 0.5) as i64;
+        if rating < 120 || rating % 120 != 0 {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "battery_rating_voltage",
+                self.battery_rating_voltage.to_string().as_bytes(),
+            ));
+        }
+
+        let blocks = rating / 120;
+        let voltage = (self.voltage * 100.0 + 0.5) as i64;
+        if !(1200 * blocks..=1525 * blocks).contains(&voltage) {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "voltage",
+                self.voltage.to_string().as_bytes(),
+            ));
+        }
+
+        Ok(Some(
+            format!("{:02}.{:02}", voltage / 100, voltage % 100).into_bytes(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbeqv::{PBEQVRequest, PBEQV};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    fn request(voltage: f32, battery_rating_voltage: f32) -> PBEQVRequest {
+        PBEQVRequest {
+            voltage,
+            battery_rating_voltage,
+        }
+    }
+
+    #[test]
+    fn test_pbeqv_payload_encode() -> Result<()> {
+        assert_eq!(request(58.4, 48.0).encode()?, Some(b"58.40".to_vec()));
+        assert_eq!(request(48.0, 48.0).encode()?, Some(b"48.00".to_vec()));
+        assert_eq!(request(61.0, 48.0).encode()?, Some(b"61.00".to_vec()));
+        assert_eq!(request(14.6, 12.0).encode()?, Some(b"14.60".to_vec()));
+        assert_eq!(request(29.2, 24.0).encode()?, Some(b"29.20".to_vec()));
+
+        assert!(request(61.1, 48.0).encode().is_err());
+        assert!(request(47.9, 48.0).encode().is_err());
+        assert!(request(58.4, 24.0).encode().is_err());
+        assert!(request(f32::NAN, 48.0).encode().is_err());
+
+        let err = request(14.6, 0.0).encode().unwrap_err();
+        assert_eq!(err.field(), Some("battery_rating_voltage"));
+        assert!(request(14.6, 18.0).encode().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqv_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBEQV>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(request(58.4, 48.0), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PBEQV58.40\x8a\x89\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbeqv_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBEQV>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
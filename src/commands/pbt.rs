@@ -0,0 +1,93 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qpiri::BatteryType;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::vec::Vec;
+
+// Sets the battery type, which selects the charging voltages unless it is `User`.
+pub struct PBT;
+
+impl Command for PBT {
+    const PROTOCOL_ID: &'static [u8] = b"PBT";
+    const COMMAND_NAME: &'static str = "SetBatteryType";
+
+    type Request = PBTRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PBTRequest {
+    pub battery_type: BatteryType,
+}
+
+impl Request for PBTRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let code = self.battery_type.code().ok_or_else(|| {
+            Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "battery_type",
+                format!("{:?}", self.battery_type).as_bytes(),
+            )
+        })?;
+
+        Ok(Some(format!("{:02}", code).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pbt::{PBTRequest, PBT};
+    use crate::commands::qpiri::BatteryType;
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pbt_payload_encode() -> Result<()> {
+        let encode = |battery_type| PBTRequest { battery_type }.encode();
+
+        assert_eq!(encode(BatteryType::AGM)?, Some(b"00".to_vec()));
+        assert_eq!(encode(BatteryType::Flooded)?, Some(b"01".to_vec()));
+        assert_eq!(encode(BatteryType::User)?, Some(b"02".to_vec()));
+        assert_eq!(encode(BatteryType::Soltaro)?, Some(b"06".to_vec()));
+
+        let err = encode(BatteryType::Unknown(String::from("7"))).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbt_command_encode() -> Result<()> {
+        let mut codec = Codec::<PBT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            PBTRequest {
+                battery_type: BatteryType::User,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"PBT02\x07\x4c\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbt_command_decode() -> Result<()> {
+        let mut codec = Codec::<PBT>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        let mut buf = BytesMut::from(frame(b"NAK").as_slice());
+        let err = codec.decode(&mut buf).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qpiri::InputVoltageRange;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::vec::Vec;
+
+// Sets the accepted grid input voltage range.
+pub struct PGR;
+
+impl Command for PGR {
+    const PROTOCOL_ID: &'static [u8] = b"PGR";
+    const COMMAND_NAME: &'static str = "SetInputVoltageRange";
+
+    type Request = PGRRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PGRRequest {
+    pub input_voltage_range: InputVoltageRange,
+}
+
+impl Request for PGRRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let code = self.input_voltage_range.code().ok_or_else(|| {
+            Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "input_voltage_range",
+                format!("{:?}", self.input_voltage_range).as_bytes(),
+            )
+        })?;
+
+        Ok(Some(format!("{:02}", code).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pgr::{PGRRequest, PGR};
+    use crate::commands::qpiri::InputVoltageRange;
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pgr_payload_encode() -> Result<()> {
+        let req = PGRRequest {
+            input_voltage_range: InputVoltageRange::Appliance,
+        };
+
+        assert_eq!(req.encode()?, Some(b"00".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pgr_command_encode() -> Result<()> {
+        let mut codec = Codec::<PGR>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            PGRRequest {
+                input_voltage_range: InputVoltageRange::UPS,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"PGR01\x39\xca\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pgr_command_decode() -> Result<()> {
+        let mut codec = Codec::<PGR>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// Sets the LED brightness.
+pub struct PLEDB;
+
+impl Command for PLEDB {
+    const PROTOCOL_ID: &'static [u8] = b"PLEDB";
+    const COMMAND_NAME: &'static str = "SetLedBrightness";
+
+    type Request = PLEDBRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PLEDBRequest {
+    // 1 (dimmest) to 9.
+    pub brightness: u8,
+}
+
+impl Request for PLEDBRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        if !(1..=9).contains(&self.brightness) {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "brightness",
+                self.brightness.to_string().as_bytes(),
+            ));
+        }
+
+        Ok(Some(format!("{}", self.brightness).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pledb::{PLEDBRequest, PLEDB};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pledb_payload_encode() -> Result<()> {
+        assert_eq!(
+            PLEDBRequest { brightness: 1 }.encode()?,
+            Some(b"1".to_vec())
+        );
+        assert_eq!(
+            PLEDBRequest { brightness: 9 }.encode()?,
+            Some(b"9".to_vec())
+        );
+        assert!(PLEDBRequest { brightness: 0 }.encode().is_err());
+        assert!(PLEDBRequest { brightness: 10 }.encode().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledb_command_encode() -> Result<()> {
+        let mut codec = Codec::<PLEDB>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PLEDBRequest { brightness: 5 }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PLEDB5\xf4\x3b\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledb_command_decode() -> Result<()> {
+        let mut codec = Codec::<PLEDB>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qled::{LedColorMode, Rgb};
+use crate::error::Result;
+use alloc::format;
+use alloc::vec::Vec;
+
+// Sets the LED colour shown in one of the device modes.
+pub struct PLEDC;
+
+impl Command for PLEDC {
+    const PROTOCOL_ID: &'static [u8] = b"PLEDC";
+    const COMMAND_NAME: &'static str = "SetLedColor";
+
+    type Request = PLEDCRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PLEDCRequest {
+    pub mode: LedColorMode,
+    pub color: Rgb,
+}
+
+impl Request for PLEDCRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(
+            format!(
+                "{}{:03}{:03}{:03}",
+                self.mode.code(),
+                self.color.red,
+                self.color.green,
+                self.color.blue
+            )
+            .into_bytes(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pledc::{PLEDCRequest, PLEDC};
+    use crate::commands::qled::{LedColorMode, Rgb};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pledc_payload_encode() -> Result<()> {
+        let req = PLEDCRequest {
+            mode: LedColorMode::BatteryMode,
+            color: Rgb::new(0, 255, 7),
+        };
+
+        assert_eq!(req.encode()?, Some(b"3000255007".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledc_command_encode() -> Result<()> {
+        let mut codec = Codec::<PLEDC>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            PLEDCRequest {
+                mode: LedColorMode::LineMode,
+                color: Rgb::new(255, 0, 128),
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"PLEDC1255000128\x58\x16\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledc_command_decode() -> Result<()> {
+        let mut codec = Codec::<PLEDC>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
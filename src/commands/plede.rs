@@ -0,0 +1,72 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::Result;
+use alloc::vec::Vec;
+
+// Turns the LED bar on or off.
+pub struct PLEDE;
+
+impl Command for PLEDE {
+    const PROTOCOL_ID: &'static [u8] = b"PLEDE";
+    const COMMAND_NAME: &'static str = "SetLedEnabled";
+
+    type Request = PLEDERequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PLEDERequest {
+    pub enabled: bool,
+}
+
+impl Request for PLEDERequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(if self.enabled { b"1" } else { b"0" }.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::plede::{PLEDERequest, PLEDE};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_plede_payload_encode() -> Result<()> {
+        assert_eq!(
+            PLEDERequest { enabled: true }.encode()?,
+            Some(b"1".to_vec())
+        );
+        assert_eq!(
+            PLEDERequest { enabled: false }.encode()?,
+            Some(b"0".to_vec())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plede_command_encode() -> Result<()> {
+        let mut codec = Codec::<PLEDE>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(PLEDERequest { enabled: true }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"PLEDE1\x2d\x28\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plede_command_decode() -> Result<()> {
+        let mut codec = Codec::<PLEDE>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
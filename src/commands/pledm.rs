@@ -0,0 +1,85 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qled::LedEffect;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::vec::Vec;
+
+// Sets the LED effect.
+pub struct PLEDM;
+
+impl Command for PLEDM {
+    const PROTOCOL_ID: &'static [u8] = b"PLEDM";
+    const COMMAND_NAME: &'static str = "SetLedEffect";
+
+    type Request = PLEDMRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PLEDMRequest {
+    pub effect: LedEffect,
+}
+
+impl Request for PLEDMRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let code = self.effect.code().ok_or_else(|| {
+            Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "effect",
+                format!("{:?}", self.effect).as_bytes(),
+            )
+        })?;
+
+        Ok(Some(format!("{}", code).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pledm::{PLEDMRequest, PLEDM};
+    use crate::commands::qled::LedEffect;
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pledm_payload_encode() -> Result<()> {
+        let req = PLEDMRequest {
+            effect: LedEffect::Solid,
+        };
+
+        assert_eq!(req.encode()?, Some(b"2".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledm_command_encode() -> Result<()> {
+        let mut codec = Codec::<PLEDM>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            PLEDMRequest {
+                effect: LedEffect::RightScrolling,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"PLEDM3\x84\xc3\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledm_command_decode() -> Result<()> {
+        let mut codec = Codec::<PLEDM>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qled::LedSpeed;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::vec::Vec;
+
+// Sets how fast the LED effect runs.
+pub struct PLEDS;
+
+impl Command for PLEDS {
+    const PROTOCOL_ID: &'static [u8] = b"PLEDS";
+    const COMMAND_NAME: &'static str = "SetLedSpeed";
+
+    type Request = PLEDSRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PLEDSRequest {
+    pub speed: LedSpeed,
+}
+
+impl Request for PLEDSRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let code = self.speed.code().ok_or_else(|| {
+            Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "speed",
+                format!("{:?}", self.speed).as_bytes(),
+            )
+        })?;
+
+        Ok(Some(format!("{}", code).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pleds::{PLEDSRequest, PLEDS};
+    use crate::commands::qled::LedSpeed;
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pleds_payload_encode() -> Result<()> {
+        let req = PLEDSRequest {
+            speed: LedSpeed::Low,
+        };
+
+        assert_eq!(req.encode()?, Some(b"0".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pleds_command_encode() -> Result<()> {
+        let mut codec = Codec::<PLEDS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            PLEDSRequest {
+                speed: LedSpeed::Fast,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"PLEDS2\xb4\x9e\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pleds_command_decode() -> Result<()> {
+        let mut codec = Codec::<PLEDS>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
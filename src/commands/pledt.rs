@@ -0,0 +1,85 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qled::LedDataPresentation;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::vec::Vec;
+
+// Sets what the LED bar shows.
+pub struct PLEDT;
+
+impl Command for PLEDT {
+    const PROTOCOL_ID: &'static [u8] = b"PLEDT";
+    const COMMAND_NAME: &'static str = "SetLedDataPresentation";
+
+    type Request = PLEDTRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct PLEDTRequest {
+    pub data_presentation: LedDataPresentation,
+}
+
+impl Request for PLEDTRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let code = self.data_presentation.code().ok_or_else(|| {
+            Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "data_presentation",
+                format!("{:?}", self.data_presentation).as_bytes(),
+            )
+        })?;
+
+        Ok(Some(format!("{}", code).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::pledt::{PLEDTRequest, PLEDT};
+    use crate::commands::qled::LedDataPresentation;
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_pledt_payload_encode() -> Result<()> {
+        let req = PLEDTRequest {
+            data_presentation: LedDataPresentation::SolarPower,
+        };
+
+        assert_eq!(req.encode()?, Some(b"1".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledt_command_encode() -> Result<()> {
+        let mut codec = Codec::<PLEDT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            PLEDTRequest {
+                data_presentation: LedDataPresentation::BatteryCapacity,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"PLEDT2\x2d\x09\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pledt_command_decode() -> Result<()> {
+        let mut codec = Codec::<PLEDT>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
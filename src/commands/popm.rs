@@ -0,0 +1,90 @@
+use crate::command::{Ack, Command, Request};
+use crate::commands::qpiri::OutputMode;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::format;
+use alloc::vec::Vec;
+
+// Output mode setter. The new mode takes effect after the inverter is restarted.
+pub struct POPM;
+
+impl Command for POPM {
+    const PROTOCOL_ID: &'static [u8] = b"POPM";
+    const COMMAND_NAME: &'static str = "SetOutputMode";
+
+    type Request = POPMRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct POPMRequest {
+    pub output_mode: OutputMode,
+}
+
+impl Request for POPMRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        let code = self.output_mode.code().ok_or_else(|| {
+            Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "output_mode",
+                format!("{:?}", self.output_mode).as_bytes(),
+            )
+        })?;
+
+        Ok(Some(format!("{:02}", code).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::popm::{POPMRequest, POPM};
+    use crate::commands::qpiri::OutputMode;
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_popm_payload_encode() -> Result<()> {
+        let req = POPMRequest {
+            output_mode: OutputMode::Phase3Of3Output,
+        };
+
+        assert_eq!(req.encode()?, Some(b"04".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_popm_command_encode() -> Result<()> {
+        let mut codec = Codec::<POPM>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            POPMRequest {
+                output_mode: OutputMode::ParallelOutput,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"POPM01\x0d\x25\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_popm_command_decode() -> Result<()> {
+        let mut codec = Codec::<POPM>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+        assert_eq!(buf.remaining(), 0);
+
+        let mut buf = BytesMut::from(frame(b"NAK").as_slice());
+        let err = codec.decode(&mut buf).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotAcknowledged));
+
+        Ok(())
+    }
+}
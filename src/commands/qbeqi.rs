@@ -0,0 +1,131 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct QBEQI;
+
+impl Command for QBEQI {
+    const PROTOCOL_ID: &'static [u8] = b"QBEQI";
+    const COMMAND_NAME: &'static str = "QueryBatteryEqualization";
+
+    type Request = ();
+    type Response = QBEQIResponse;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct QBEQIResponse {
+    pub enabled: bool,
+    // Minutes.
+    pub time: u16,
+    // Days between equalizations.
+    pub period: u16,
+    pub max_current: u16,
+    pub voltage: f32,
+    // Minutes the equalization may be extended by.
+    pub over_time: u16,
+    pub active: bool,
+    // Hours since the last equalization.
+    pub elapsed_time: u16,
+}
+
+impl Response for QBEQIResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 10..=10)?;
+
+        let enabled = fields.flag("enabled")?;
+        let time = fields.parse("time")?;
+        let period = fields.parse("period")?;
+        let max_current = fields.parse("max_current")?;
+        fields.skip("reserved")?;
+        let voltage = fields.parse("voltage")?;
+        fields.skip("reserved")?;
+        let over_time = fields.parse("over_time")?;
+        let active = fields.flag("active")?;
+        let elapsed_time = fields.parse("elapsed_time")?;
+
+        Ok(Self {
+            enabled,
+            time,
+            period,
+            max_current,
+            voltage,
+            over_time,
+            active,
+            elapsed_time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qbeqi::{QBEQIResponse, QBEQI};
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qbeqi_payload_encode() -> Result<()> {
+        let req: <QBEQI as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qbeqi_payload_decode() -> Result<()> {
+        let item = <QBEQI as Command>::Response::decode(b"1 030 030 080 021 55.40 224 030 0 0234")?;
+        assert_eq!(
+            item,
+            QBEQIResponse {
+                enabled: true,
+                time: 30,
+                period: 30,
+                max_current: 80,
+                voltage: 55.4,
+                over_time: 30,
+                active: false,
+                elapsed_time: 234,
+            }
+        );
+
+        let err = <QBEQI as Command>::Response::decode(b"2 030 030 080 021 55.40 224 030 0 0234")
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+        assert_eq!(err.field(), Some("enabled"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qbeqi_command_encode() -> Result<()> {
+        let mut codec = Codec::<QBEQI>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QBEQI\x2e\xa9\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qbeqi_command_decode() -> Result<()> {
+        let mut codec = Codec::<QBEQI>::new();
+
+        let mut buf = BytesMut::from(frame(b"0 060 090 060 021 58.40 224 120 1 0000").as_slice());
+        let item = codec.decode(&mut buf)?.unwrap();
+
+        assert_eq!(buf.remaining(), 0);
+        assert!(!item.enabled);
+        assert!(item.active);
+        assert_eq!(item.voltage, 58.4);
+        assert_eq!(item.over_time, 120);
+
+        Ok(())
+    }
+}
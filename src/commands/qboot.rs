@@ -0,0 +1,94 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct QBOOT;
+
+impl Command for QBOOT {
+    const PROTOCOL_ID: &'static [u8] = b"QBOOT";
+    const COMMAND_NAME: &'static str = "QueryDspBootstrap";
+
+    type Request = ();
+    type Response = QBOOTResponse;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QBOOTResponse {
+    // Whether the DSP has a bootstrap, i.e. its firmware can be updated.
+    pub has_bootstrap: bool,
+}
+
+impl Response for QBOOTResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 1..=1)?;
+
+        Ok(Self {
+            has_bootstrap: fields.flag("has_bootstrap")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qboot::{QBOOTResponse, QBOOT};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qboot_payload_encode() -> Result<()> {
+        let req: <QBOOT as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qboot_payload_decode() -> Result<()> {
+        assert_eq!(
+            <QBOOT as Command>::Response::decode(b"1")?,
+            QBOOTResponse {
+                has_bootstrap: true
+            }
+        );
+        assert!(!<QBOOT as Command>::Response::decode(b"0")?.has_bootstrap);
+        assert!(<QBOOT as Command>::Response::decode(b"2").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qboot_command_encode() -> Result<()> {
+        let mut codec = Codec::<QBOOT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QBOOT\x0a\x88\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qboot_command_decode() -> Result<()> {
+        let mut codec = Codec::<QBOOT>::new();
+
+        let mut buf = BytesMut::from(frame(b"0").as_slice());
+        let item = codec.decode(&mut buf)?;
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(
+            item,
+            Some(QBOOTResponse {
+                has_bootstrap: false
+            })
+        );
+
+        Ok(())
+    }
+}
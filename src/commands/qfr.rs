@@ -0,0 +1,168 @@
+use crate::command::{Command, Request, Response};
+use crate::datetime::DateTime;
+use crate::error::{Error, ErrorKind, Result};
+use crate::fault::FaultCode;
+use crate::tokenizer::Tokenizer;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use serde_derive::Serialize;
+
+// Fault history query, only found on some firmwares. `QFRnn` returns the `nn`-th most recent
+// fault as `FF YYYYMMDDhhmmss`, or a zero fault code for empty slots.
+pub struct QFR;
+
+impl Command for QFR {
+    const PROTOCOL_ID: &'static [u8] = b"QFR";
+    const COMMAND_NAME: &'static str = "QueryFaultRecord";
+
+    type Request = QFRRequest;
+    type Response = QFRResponse;
+}
+
+#[derive(Debug)]
+pub struct QFRRequest {
+    // Zero is the most recent fault.
+    pub index: u8,
+}
+
+impl QFRRequest {
+    pub const MAX_INDEX: u8 = 99;
+}
+
+impl Request for QFRRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        if self.index > Self::MAX_INDEX {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "index",
+                self.index.to_string().as_bytes(),
+            ));
+        }
+
+        Ok(Some(format!("{:02}", self.index).into_bytes()))
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct QFRResponse {
+    pub record: Option<FaultRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FaultRecord {
+    pub fault_code: FaultCode,
+    pub timestamp: DateTime,
+}
+
+impl Response for QFRResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 2..=2)?;
+
+        Ok(Self {
+            record: match FaultCode::parse(fields.str("fault_code")?, "fault_code")? {
+                Some(fault_code) => Some(FaultRecord {
+                    fault_code,
+                    timestamp: DateTime::parse(fields.str("timestamp")?, "timestamp")?,
+                }),
+                None => None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qfr::{QFRRequest, QFR};
+    use crate::datetime::DateTime;
+    use crate::error::Result;
+    use crate::fault::FaultCode;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_qfr_payload_encode() -> Result<()> {
+        let req: <QFR as Command>::Request = QFRRequest { index: 3 };
+        assert_eq!(req.encode()?, Some(b"03".to_vec()));
+
+        assert!(QFRRequest { index: 100 }.encode().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_qfr_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("02 20200817132001");
+        let item = <QFR as Command>::Response::decode(&buf)?;
+        let record = item.record.unwrap();
+
+        assert_eq!(record.fault_code, FaultCode::OverTemperature);
+        assert_eq!(
+            record.timestamp,
+            DateTime {
+                year: 2020,
+                month: 8,
+                day: 17,
+                hour: 13,
+                minute: 20,
+                second: 1,
+            }
+        );
+
+        let buf = BytesMut::from("00 00000000000000");
+        assert_eq!(<QFR as Command>::Response::decode(&buf)?.record, None);
+
+        let buf = BytesMut::from("02 20201317132001");
+        assert!(<QFR as Command>::Response::decode(&buf).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qfr_command_encode() -> Result<()> {
+        let mut codec = Codec::<QFR>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(QFRRequest { index: 0 }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QFR00\xf5\x0e\r");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_qfr_fault_history() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QFR00", b"58 20200817132001")
+            .respond(b"QFR01", b"01 20200816080000")
+            .respond(b"QFR02", b"00 00000000000000");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let records = inverter.fault_history(10).await?;
+        assert_eq!(
+            records.iter().map(|x| x.fault_code).collect::<Vec<_>>(),
+            vec![FaultCode::OutputVoltageTooLow, FaultCode::FanLocked]
+        );
+        assert_eq!(inverter.into_inner().requests.len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_qfr_fault_history_limit() -> Result<()> {
+        let stream = (0..=QFRRequest::MAX_INDEX).fold(MockStream::new(), |stream, index| {
+            stream.respond(format!("QFR{:02}", index).as_bytes(), b"01 20200816080000")
+        });
+        let mut inverter = Inverter::from_stream(stream);
+
+        // Only the indices the device can be asked for are read.
+        let records = inverter.fault_history(150).await?;
+        assert_eq!(records.len(), 100);
+        assert_eq!(inverter.into_inner().requests.len(), 100);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use crate::command::{Command, Response};
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use serde_derive::Serialize;
+
+pub struct QGMN;
+
+impl Command for QGMN {
+    const PROTOCOL_ID: &'static [u8] = b"QGMN";
+    const COMMAND_NAME: &'static str = "QueryGeneralModelNumber";
+
+    type Request = ();
+    type Response = QGMNResponse;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QGMNResponse {
+    // Kept as sent (e.g. `044`), as it is looked up rather than computed with.
+    pub general_model_number: String,
+}
+
+impl Response for QGMNResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 1..=1)?;
+        let general_model_number = fields.str("general_model_number")?;
+        if general_model_number.is_empty() {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "general_model_number",
+                src,
+            ));
+        }
+
+        Ok(Self {
+            general_model_number: general_model_number.to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qgmn::{QGMNResponse, QGMN};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qgmn_payload_encode() -> Result<()> {
+        let req: <QGMN as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qgmn_payload_decode() -> Result<()> {
+        let item = <QGMN as Command>::Response::decode(b"044")?;
+        assert_eq!(
+            item,
+            QGMNResponse {
+                general_model_number: String::from("044")
+            }
+        );
+
+        assert!(<QGMN as Command>::Response::decode(b"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qgmn_command_encode() -> Result<()> {
+        let mut codec = Codec::<QGMN>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QGMN\x49\x28\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qgmn_command_decode() -> Result<()> {
+        let mut codec = Codec::<QGMN>::new();
+
+        let mut buf = BytesMut::from(frame(b"044").as_slice());
+        let item = codec.decode(&mut buf)?;
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(item.unwrap().general_model_number, "044");
+
+        Ok(())
+    }
+}
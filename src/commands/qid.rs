@@ -1,8 +1,9 @@
 use crate::command::{Command, Response};
-use crate::error::Result;
-use bytes::BytesMut;
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
 use serde_derive::Serialize;
-use std::str::FromStr;
 
 pub struct QID;
 
@@ -14,15 +15,26 @@ impl Command for QID {
     type Response = QIDResponse;
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct QIDResponse {
-    pub(crate) serial_number: u64,
+    // Kept as sent, leading zeros included.
+    pub serial_number: String,
 }
 
 impl Response for QIDResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 1..=1)?;
+        let serial_number = fields.str("serial_number")?;
+        if serial_number.is_empty() {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "serial_number",
+                src,
+            ));
+        }
+
         Ok(Self {
-            serial_number: u64::from_str(std::str::from_utf8(src.as_ref())?)?,
+            serial_number: serial_number.to_owned(),
         })
     }
 }
@@ -53,12 +65,16 @@ mod test {
             let n: u64 = random();
             let res = format!("{}", n);
 
-            let mut buf = BytesMut::from(res.as_str());
-            let item = <QID as Command>::Response::decode(&mut buf)?;
+            let buf = BytesMut::from(res.as_str());
+            let item = <QID as Command>::Response::decode(&buf)?;
 
-            assert_eq!(item, QIDResponse { serial_number: n });
+            assert_eq!(item, QIDResponse { serial_number: res });
         }
 
+        let item = <QID as Command>::Response::decode(b"0092932105105335")?;
+        assert_eq!(item.serial_number, "0092932105105335");
+        assert!(<QID as Command>::Response::decode(b"").is_err());
+
         Ok(())
     }
 
@@ -91,7 +107,12 @@ mod test {
             let item = codec.decode(&mut buf)?;
 
             assert_eq!(buf.remaining(), 0);
-            assert_eq!(item, Some(QIDResponse { serial_number: n }));
+            assert_eq!(
+                item,
+                Some(QIDResponse {
+                    serial_number: n.to_string()
+                })
+            );
         }
 
         Ok(())
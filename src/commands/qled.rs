@@ -0,0 +1,377 @@
+use crate::command::{Command, Response};
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::{FromField, Tokenizer};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::str::from_utf8;
+use serde_derive::Serialize;
+
+pub struct QLED;
+
+impl Command for QLED {
+    const PROTOCOL_ID: &'static [u8] = b"QLED";
+    const COMMAND_NAME: &'static str = "QueryLedStatus";
+
+    type Request = ();
+    type Response = QLEDResponse;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct QLEDResponse {
+    pub enabled: bool,
+    pub speed: LedSpeed,
+    pub effect: LedEffect,
+    // 1 to 9.
+    pub brightness: u8,
+    pub data_presentation: LedDataPresentation,
+    pub colors: LedColors,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum LedSpeed {
+    Low,
+    Medium,
+    Fast,
+    Unknown(String),
+}
+
+impl LedSpeed {
+    /// Decode the numeric code used by `QLED` and `PLEDS`.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::Low,
+            1 => Self::Medium,
+            2 => Self::Fast,
+            _ => return None,
+        })
+    }
+
+    pub fn code(&self) -> Option<u8> {
+        Some(match self {
+            Self::Low => 0,
+            Self::Medium => 1,
+            Self::Fast => 2,
+            Self::Unknown(_) => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum LedEffect {
+    Breathing,
+    Solid,
+    RightScrolling,
+    Unknown(String),
+}
+
+impl LedEffect {
+    /// Decode the numeric code used by `QLED` and `PLEDM`. Code 1 is reserved.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::Breathing,
+            2 => Self::Solid,
+            3 => Self::RightScrolling,
+            _ => return None,
+        })
+    }
+
+    pub fn code(&self) -> Option<u8> {
+        Some(match self {
+            Self::Breathing => 0,
+            Self::Solid => 2,
+            Self::RightScrolling => 3,
+            Self::Unknown(_) => return None,
+        })
+    }
+}
+
+/// What the length of the LED bar shows.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum LedDataPresentation {
+    SolarPower,
+    BatteryCapacity,
+    LoadPercentage,
+    Unknown(String),
+}
+
+impl LedDataPresentation {
+    /// Decode the numeric code used by `QLED` and `PLEDT`.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            1 => Self::SolarPower,
+            2 => Self::BatteryCapacity,
+            3 => Self::LoadPercentage,
+            _ => return None,
+        })
+    }
+
+    pub fn code(&self) -> Option<u8> {
+        Some(match self {
+            Self::SolarPower => 1,
+            Self::BatteryCapacity => 2,
+            Self::LoadPercentage => 3,
+            Self::Unknown(_) => return None,
+        })
+    }
+}
+
+/// The device modes that each have their own LED colour.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum LedColorMode {
+    LineMode,
+    AvrMode,
+    BatteryMode,
+}
+
+impl LedColorMode {
+    /// Decode the numeric code used by `PLEDC`.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            1 => Self::LineMode,
+            2 => Self::AvrMode,
+            3 => Self::BatteryMode,
+            _ => return None,
+        })
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::LineMode => 1,
+            Self::AvrMode => 2,
+            Self::BatteryMode => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Rgb {
+    pub fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    // Three digits per component.
+    fn decode(src: &[u8]) -> Result<Self> {
+        let component = |index: usize| {
+            u8::from_field(&src[index..index + 3]).map_err(|e| e.with_field("colors", src))
+        };
+
+        Ok(Self {
+            red: component(0)?,
+            green: component(3)?,
+            blue: component(6)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LedColors {
+    pub line_mode: Rgb,
+    pub avr_mode: Rgb,
+    pub battery_mode: Rgb,
+}
+
+impl LedColors {
+    pub fn get(&self, mode: LedColorMode) -> Rgb {
+        match mode {
+            LedColorMode::LineMode => self.line_mode,
+            LedColorMode::AvrMode => self.avr_mode,
+            LedColorMode::BatteryMode => self.battery_mode,
+        }
+    }
+}
+
+impl Response for QLEDResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl QLEDResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 6..=6)?;
+
+        let enabled = fields.flag("enabled")?;
+        let speed = decode_code(
+            fields.raw("speed")?,
+            "speed",
+            lenient,
+            LedSpeed::from_code,
+            LedSpeed::Unknown,
+        )?;
+        let effect = decode_code(
+            fields.raw("effect")?,
+            "effect",
+            lenient,
+            LedEffect::from_code,
+            LedEffect::Unknown,
+        )?;
+        let brightness = fields.raw("brightness")?;
+        let brightness = match brightness {
+            [x @ b'1'..=b'9'] => x - b'0',
+            _ => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidPayload,
+                    "brightness",
+                    brightness,
+                ))
+            }
+        };
+        let data_presentation = decode_code(
+            fields.raw("data_presentation")?,
+            "data_presentation",
+            lenient,
+            LedDataPresentation::from_code,
+            LedDataPresentation::Unknown,
+        )?;
+
+        // The three colours are sent back to back, in the order of their `PLEDC` codes.
+        let colors = fields.raw("colors")?;
+        if colors.len() != 27 {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "colors",
+                colors,
+            ));
+        }
+        let colors = LedColors {
+            line_mode: Rgb::decode(&colors[0..9])?,
+            avr_mode: Rgb::decode(&colors[9..18])?,
+            battery_mode: Rgb::decode(&colors[18..27])?,
+        };
+
+        Ok(Self {
+            enabled,
+            speed,
+            effect,
+            brightness,
+            data_presentation,
+            colors,
+        })
+    }
+}
+
+fn decode_code<T>(
+    src: &[u8],
+    field: &'static str,
+    lenient: bool,
+    from_code: fn(u8) -> Option<T>,
+    unknown: fn(String) -> T,
+) -> Result<T> {
+    let code = match src {
+        [x @ b'0'..=b'9'] => from_code(x - b'0'),
+        _ => None,
+    };
+
+    match (code, from_utf8(src)) {
+        (Some(x), _) => Ok(x),
+        (None, Ok(x)) if lenient => Ok(unknown(x.to_owned())),
+        _ => Err(Error::invalid_field(ErrorKind::InvalidPayload, field, src)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qled::{
+        LedColorMode, LedColors, LedDataPresentation, LedEffect, LedSpeed, QLEDResponse, Rgb, QLED,
+    };
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qled_payload_encode() -> Result<()> {
+        let req: <QLED as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qled_payload_decode() -> Result<()> {
+        let item = <QLED as Command>::Response::decode(b"1 1 2 5 3 148000211255255255000255255")?;
+        assert_eq!(
+            item,
+            QLEDResponse {
+                enabled: true,
+                speed: LedSpeed::Medium,
+                effect: LedEffect::Solid,
+                brightness: 5,
+                data_presentation: LedDataPresentation::LoadPercentage,
+                colors: LedColors {
+                    line_mode: Rgb::new(148, 0, 211),
+                    avr_mode: Rgb::new(255, 255, 255),
+                    battery_mode: Rgb::new(0, 255, 255),
+                },
+            }
+        );
+        assert_eq!(
+            item.colors.get(LedColorMode::BatteryMode),
+            Rgb::new(0, 255, 255)
+        );
+
+        // Reserved effect, brightness out of range, a component over 255 and a short colour list.
+        assert!(
+            <QLED as Command>::Response::decode(b"1 1 1 5 3 148000211255255255000255255").is_err()
+        );
+        assert!(
+            <QLED as Command>::Response::decode(b"1 1 2 0 3 148000211255255255000255255").is_err()
+        );
+        assert!(
+            <QLED as Command>::Response::decode(b"1 1 2 5 3 148000211255255256000255255").is_err()
+        );
+        assert!(<QLED as Command>::Response::decode(b"1 1 2 5 3 148000211255255255").is_err());
+
+        let err = <QLED as Command>::Response::decode(b"1 1 2 5 3").unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 6,
+                actual: 5
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qled_command_encode() -> Result<()> {
+        let mut codec = Codec::<QLED>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QLED\x91\x3a\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qled_command_decode() -> Result<()> {
+        let mut codec = Codec::<QLED>::new();
+
+        let mut buf = BytesMut::from(frame(b"0 0 0 9 1 000000255255000000000255000").as_slice());
+        let item = codec.decode(&mut buf)?.unwrap();
+
+        assert_eq!(buf.remaining(), 0);
+        assert!(!item.enabled);
+        assert_eq!(item.effect, LedEffect::Breathing);
+        assert_eq!(item.data_presentation, LedDataPresentation::SolarPower);
+        assert_eq!(item.colors.line_mode, Rgb::new(0, 0, 255));
+
+        Ok(())
+    }
+}
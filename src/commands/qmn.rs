@@ -0,0 +1,100 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::str::from_utf8;
+use serde_derive::Serialize;
+
+pub struct QMN;
+
+impl Command for QMN {
+    const PROTOCOL_ID: &'static [u8] = b"QMN";
+    const COMMAND_NAME: &'static str = "QueryModelName";
+
+    type Request = ();
+    type Response = QMNResponse;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QMNResponse {
+    pub model_name: String,
+}
+
+impl Response for QMNResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Ok(Self {
+            model_name: from_utf8(src)?.trim().to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qmn::{QMNResponse, QMN};
+    use crate::error::Result;
+    use bytes::{Buf, BytesMut};
+    use crc_any::CRCu16;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qmn_payload_encode() -> Result<()> {
+        let req: <QMN as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qmn_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("MKS2-8000");
+        let item = <QMN as Command>::Response::decode(&buf)?;
+
+        assert_eq!(
+            item,
+            QMNResponse {
+                model_name: String::from("MKS2-8000")
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qmn_command_encode() -> Result<()> {
+        let mut codec = Codec::<QMN>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QMN\xbb\x64\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qmn_command_decode() -> Result<()> {
+        let mut codec = Codec::<QMN>::new();
+
+        let mut res = String::from("(VMII-NXPW5KW").into_bytes();
+        let mut crc_sum = CRCu16::crc16xmodem();
+        crc_sum.digest(res.as_slice());
+        res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+        res.push(b'\r');
+
+        let mut buf = BytesMut::from(res.as_slice());
+        let item = codec.decode(&mut buf)?;
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(
+            item,
+            Some(QMNResponse {
+                model_name: String::from("VMII-NXPW5KW")
+            })
+        );
+
+        Ok(())
+    }
+}
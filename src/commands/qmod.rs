@@ -2,10 +2,11 @@ use crate::command::{Command, Response};
 use crate::commands::qmod::DeviceMode::{
     BatteryMode, FaultMode, LineMode, PowerOnMode, PowerSavingMode, StandbyMode,
 };
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
 use serde_derive::Serialize;
-use std::str::from_utf8;
 
 pub struct QMOD;
 
@@ -22,7 +23,7 @@ pub struct QMODResponse {
     pub(crate) mode: DeviceMode,
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum DeviceMode {
     PowerOnMode,
     StandbyMode,
@@ -30,19 +31,39 @@ pub enum DeviceMode {
     BatteryMode,
     FaultMode,
     PowerSavingMode,
+    Unknown(String),
 }
 
 impl Response for QMODResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl QMODResponse {
+    pub(crate) fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 1..=1)?;
+
         Ok(Self {
-            mode: match from_utf8(&src[0..1])? {
+            mode: match fields.str("mode")? {
                 "P" => PowerOnMode,
                 "S" => StandbyMode,
                 "L" => LineMode,
                 "B" => BatteryMode,
                 "F" => FaultMode,
                 "H" => PowerSavingMode,
-                _ => return Err(Error::InvalidDeviceMode),
+                x if lenient => DeviceMode::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceMode,
+                        "mode",
+                        x.as_bytes(),
+                    ))
+                }
             },
         })
     }
@@ -56,7 +77,7 @@ mod test {
         BatteryMode, FaultMode, LineMode, PowerOnMode, PowerSavingMode, StandbyMode,
     };
     use crate::commands::qmod::{QMODResponse, QMOD};
-    use crate::error::Result;
+    use crate::error::{ErrorKind, Result};
     use bytes::{Buf, BytesMut};
     use crc_any::CRCu16;
     use rand::{thread_rng, Rng};
@@ -79,8 +100,8 @@ mod test {
             let mut rng = thread_rng();
             let mode = device_mode_options[rng.gen_range(0, device_mode_options.len())];
 
-            let mut buf = BytesMut::from(mode);
-            let item = <QMOD as Command>::Response::decode(&mut buf)?;
+            let buf = BytesMut::from(mode);
+            let item = <QMOD as Command>::Response::decode(&buf)?;
 
             assert_eq!(
                 item,
@@ -101,6 +122,21 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_qmod_payload_decode_field_count() {
+        let err = <QMOD as Command>::Response::decode(b"").unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidDeviceMode));
+
+        let err = <QMOD as Command>::Response::decode(b"B B").unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooManyFields {
+                expected: 1,
+                actual: 2
+            }
+        ));
+    }
+
     #[test]
     fn test_qmod_command_encode() -> Result<()> {
         let mut codec = Codec::<QMOD>::new();
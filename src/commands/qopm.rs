@@ -0,0 +1,127 @@
+use crate::command::{Command, Response};
+use crate::commands::qpiri::OutputMode;
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use core::str::from_utf8;
+use serde_derive::Serialize;
+
+pub struct QOPM;
+
+impl Command for QOPM {
+    const PROTOCOL_ID: &'static [u8] = b"QOPM";
+    const COMMAND_NAME: &'static str = "QueryOutputMode";
+
+    type Request = ();
+    type Response = QOPMResponse;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct QOPMResponse {
+    pub output_mode: OutputMode,
+}
+
+impl Response for QOPMResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl QOPMResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 1..=1)?;
+        let code = fields.raw("output_mode")?;
+
+        Ok(Self {
+            output_mode: match decode_output_mode(code) {
+                Some(x) => x,
+                None if lenient => OutputMode::Unknown(from_utf8(code)?.to_owned()),
+                None => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceOutputMode,
+                        "output_mode",
+                        code,
+                    ))
+                }
+            },
+        })
+    }
+}
+
+// Sent as two digits.
+fn decode_output_mode(src: &[u8]) -> Option<OutputMode> {
+    match src {
+        [b'0', x @ b'0'..=b'9'] => OutputMode::from_code(x - b'0'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qopm::{QOPMResponse, QOPM};
+    use crate::commands::qpiri::OutputMode;
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qopm_payload_encode() -> Result<()> {
+        let req: <QOPM as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qopm_payload_decode() -> Result<()> {
+        for code in 0..5 {
+            let item = <QOPM as Command>::Response::decode(format!("{:02}", code).as_bytes())?;
+            assert_eq!(item.output_mode, OutputMode::from_code(code).unwrap());
+        }
+
+        for src in &[&b"05"[..], b"0", b"1", b"001"] {
+            let err = <QOPM as Command>::Response::decode(src).unwrap_err();
+            assert!(matches!(err.kind(), ErrorKind::InvalidDeviceOutputMode));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qopm_command_encode() -> Result<()> {
+        let mut codec = Codec::<QOPM>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QOPM\xa5\xc5\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qopm_command_decode() -> Result<()> {
+        let mut codec = Codec::<QOPM>::new();
+
+        let mut buf = BytesMut::from(frame(b"03").as_slice());
+        let item = codec.decode(&mut buf)?;
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(
+            item,
+            Some(QOPMResponse {
+                output_mode: OutputMode::Phase2Of3Output
+            })
+        );
+
+        Ok(())
+    }
+}
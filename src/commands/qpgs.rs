@@ -0,0 +1,226 @@
+use crate::command::{Command, Request, Response};
+use crate::commands::qmod::{DeviceMode, QMODResponse};
+use crate::commands::qpiri::ChargeSourcePriority::{
+    GridFirst, OnlySolar, SolarAndGrid, SolarFirst,
+};
+use crate::commands::qpiri::OutputMode::{
+    ParallelOutput, Phase1Of3Output, Phase2Of3Output, Phase3Of3Output, SingleMachineOutput,
+};
+use crate::commands::qpiri::{ChargeSourcePriority, OutputMode};
+use crate::error::{Error, ErrorKind, Result};
+use crate::fault::FaultCode;
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde_derive::Serialize;
+
+pub struct QPGS;
+
+impl Command for QPGS {
+    const PROTOCOL_ID: &'static [u8] = b"QPGS";
+    const COMMAND_NAME: &'static str = "QueryParallelInformation";
+
+    type Request = QPGSRequest;
+    type Response = QPGSResponse;
+}
+
+#[derive(Debug)]
+pub struct QPGSRequest {
+    // Index of the machine within the parallel system.
+    pub machine: u8,
+}
+
+impl Request for QPGSRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(self.machine.to_string().into_bytes()))
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct QPGSResponse {
+    pub parallel_exists: bool,
+    pub serial_number: String,
+    pub mode: DeviceMode,
+    pub fault_code: Option<FaultCode>,
+    pub grid_voltage: f32,
+    pub grid_frequency: f32,
+    pub ac_out_voltage: f32,
+    pub ac_out_frequency: f32,
+    pub ac_out_apparent_power: usize,
+    pub ac_out_active_power: usize,
+    pub out_load_percent: usize,
+    pub battery_voltage: f32,
+    pub battery_charge_current: usize,
+    pub battery_capacity: usize,
+    pub pv_input_voltage: f32,
+    pub total_charge_current: usize,
+    pub total_ac_out_apparent_power: usize,
+    pub total_ac_out_active_power: usize,
+    pub total_out_load_percent: usize,
+    pub output_mode: OutputMode,
+    pub charge_source_priority: ChargeSourcePriority,
+    pub max_charging_current: usize,
+    pub max_ac_charging_current: usize,
+    pub pv_input_current: usize,
+    pub battery_discharge_current: usize,
+}
+
+impl Response for QPGSResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl QPGSResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 27..=27)?;
+
+        Ok(Self {
+            parallel_exists: fields.flag("parallel_exists")?,
+            serial_number: fields.str("serial_number")?.to_owned(),
+            mode: QMODResponse::decode_with(fields.raw("mode")?, lenient)?.mode,
+            fault_code: FaultCode::parse(fields.str("fault_code")?, "fault_code")?,
+            grid_voltage: fields.parse("grid_voltage")?,
+            grid_frequency: fields.parse("grid_frequency")?,
+            ac_out_voltage: fields.parse("ac_out_voltage")?,
+            ac_out_frequency: fields.parse("ac_out_frequency")?,
+            ac_out_apparent_power: fields.parse("ac_out_apparent_power")?,
+            ac_out_active_power: fields.parse("ac_out_active_power")?,
+            out_load_percent: fields.parse("out_load_percent")?,
+            battery_voltage: fields.parse("battery_voltage")?,
+            battery_charge_current: fields.parse("battery_charge_current")?,
+            battery_capacity: fields.parse("battery_capacity")?,
+            pv_input_voltage: fields.parse("pv_input_voltage")?,
+            total_charge_current: fields.parse("total_charge_current")?,
+            total_ac_out_apparent_power: fields.parse("total_ac_out_apparent_power")?,
+            total_ac_out_active_power: fields.parse("total_ac_out_active_power")?,
+            total_out_load_percent: fields.parse("total_out_load_percent")?,
+            output_mode: {
+                fields.skip("inverter_status")?;
+                match fields.str("output_mode")? {
+                    "0" => SingleMachineOutput,
+                    "1" => ParallelOutput,
+                    "2" => Phase1Of3Output,
+                    "3" => Phase2Of3Output,
+                    "4" => Phase3Of3Output,
+                    x if lenient => OutputMode::Unknown(x.to_owned()),
+                    x => {
+                        return Err(Error::invalid_field(
+                            ErrorKind::InvalidDeviceOutputMode,
+                            "output_mode",
+                            x.as_bytes(),
+                        ))
+                    }
+                }
+            },
+            charge_source_priority: match fields.str("charge_source_priority")? {
+                "0" => GridFirst,
+                "1" => SolarFirst,
+                "2" => SolarAndGrid,
+                "3" => OnlySolar,
+                x if lenient => ChargeSourcePriority::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceChargeSourcePriority,
+                        "charge_source_priority",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            max_charging_current: fields.parse("max_charging_current")?,
+            max_ac_charging_current: {
+                fields.skip("max_charging_current_range")?;
+                fields.parse("max_ac_charging_current")?
+            },
+            pv_input_current: fields.parse("pv_input_current")?,
+            battery_discharge_current: fields.parse("battery_discharge_current")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qmod::DeviceMode;
+    use crate::commands::qpgs::{QPGSRequest, QPGS};
+    use crate::commands::qpiri::{ChargeSourcePriority, OutputMode};
+    use crate::error::{ErrorKind, Result};
+    use crate::fault::FaultCode;
+    use bytes::{Buf, BytesMut};
+    use crc_any::CRCu16;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    const RESPONSE: &str = "1 92931701100510 F 58 000.0 00.00 230.0 50.00 0275 0213 005 51.4 001 100 083.3 002 00584 00451 004 10100010 1 2 060 120 10 04 000";
+
+    #[test]
+    fn test_qpgs_payload_encode() -> Result<()> {
+        let req: <QPGS as Command>::Request = QPGSRequest { machine: 1 };
+        assert_eq!(req.encode()?, Some(b"1".to_vec()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_qpgs_payload_decode() -> Result<()> {
+        let buf = BytesMut::from(RESPONSE);
+        let item = <QPGS as Command>::Response::decode(&buf)?;
+
+        assert!(item.parallel_exists);
+        assert_eq!(item.serial_number, "92931701100510");
+        assert_eq!(item.mode, DeviceMode::FaultMode);
+        assert_eq!(item.fault_code, Some(FaultCode::OutputVoltageTooLow));
+        assert_eq!(item.ac_out_voltage, 230.0);
+        assert_eq!(item.total_ac_out_active_power, 451);
+        assert_eq!(item.output_mode, OutputMode::ParallelOutput);
+        assert_eq!(
+            item.charge_source_priority,
+            ChargeSourcePriority::SolarAndGrid
+        );
+        assert_eq!(item.max_ac_charging_current, 10);
+        assert_eq!(item.pv_input_current, 4);
+
+        let buf = BytesMut::from(&RESPONSE[..40]);
+        assert!(<QPGS as Command>::Response::decode(&buf).is_err());
+
+        let res = format!("2{}", &RESPONSE[1..]);
+        let err = <QPGS as Command>::Response::decode(res.as_bytes()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+        assert_eq!(err.field(), Some("parallel_exists"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qpgs_command_encode() -> Result<()> {
+        let mut codec = Codec::<QPGS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(QPGSRequest { machine: 0 }, &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QPGS0\x3f\xda\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qpgs_command_decode() -> Result<()> {
+        let mut codec = Codec::<QPGS>::new();
+
+        let mut res = format!("({}", RESPONSE).into_bytes();
+        let mut crc_sum = CRCu16::crc16xmodem();
+        crc_sum.digest(res.as_slice());
+        res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+        res.push(b'\r');
+
+        let mut buf = BytesMut::from(res.as_slice());
+        let item = codec.decode(&mut buf)?.unwrap();
+        assert_eq!(item.fault_code, Some(FaultCode::OutputVoltageTooLow));
+
+        Ok(())
+    }
+}
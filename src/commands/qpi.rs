@@ -1,8 +1,7 @@
 use crate::command::{Command, Response};
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::{Error, ErrorKind, Result};
+use core::str::FromStr;
 use serde_derive::Serialize;
-use std::str::FromStr;
 
 pub struct QPI;
 
@@ -20,13 +19,17 @@ pub struct QPIResponse {
 }
 
 impl Response for QPIResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
+    fn decode(src: &[u8]) -> Result<Self> {
         if !src.starts_with(b"PI") {
-            return Err(Error::InvalidPayload(None));
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "protocol_id",
+                src,
+            ));
         }
 
         Ok(Self {
-            protocol_id: u64::from_str(std::str::from_utf8(src[2..].as_ref())?)?,
+            protocol_id: u64::from_str(core::str::from_utf8(&src[2..])?)?,
         })
     }
 }
@@ -57,8 +60,8 @@ mod test {
             let n: u64 = random();
             let res = format!("PI{}", n);
 
-            let mut buf = BytesMut::from(res.as_str());
-            let item = <QPI as Command>::Response::decode(&mut buf)?;
+            let buf = BytesMut::from(res.as_str());
+            let item = <QPI as Command>::Response::decode(&buf)?;
 
             assert_eq!(item, QPIResponse { protocol_id: n });
         }
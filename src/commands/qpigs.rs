@@ -2,11 +2,12 @@ use crate::command::{Command, Response};
 use crate::commands::qpigs::DeviceChargingStatus::{
     ChargingFromAC, ChargingFromSCC, ChargingFromSCCAndAC, NotCharging,
 };
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::str::from_utf8;
 use serde_derive::Serialize;
-use std::str::from_utf8;
-use std::str::FromStr;
 
 pub struct QPIGS;
 
@@ -37,6 +38,8 @@ pub struct QPIGSResponse {
     pub battery_scc_voltage: f32,
     pub battery_discharge_current: usize,
     pub device_status: DeviceStatus,
+    // Left out by older firmware.
+    pub pv_charging_power: Option<usize>,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -51,41 +54,54 @@ pub enum DeviceChargingStatus {
     ChargingFromSCC,
     ChargingFromAC,
     ChargingFromSCCAndAC,
+    Unknown(String),
 }
 
 impl Response for QPIGSResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
-        // println!("Input: {:?}", from_utf8(&src)?);
-
-        // Extract indices
-        let mut idxs = [0usize; 22];
-        src.iter()
-            .cloned()
-            .enumerate()
-            .filter(|(_, x)| *x == ' ' as u8)
-            .fold(0, |num_idx, byte_idx| {
-                idxs[num_idx] = byte_idx.0;
-                num_idx + 1
-            });
-
-        // Extract data
-        let grid_voltage = f32::from_str(from_utf8(&src[0..idxs[0]])?)?;
-        let grid_frequency = f32::from_str(from_utf8(&src[idxs[0] + 1..idxs[1]])?)?;
-        let ac_out_voltage = f32::from_str(from_utf8(&src[idxs[1] + 1..idxs[2]])?)?;
-        let ac_out_frequency = f32::from_str(from_utf8(&src[idxs[2] + 1..idxs[3]])?)?;
-        let ac_out_apparent_power = usize::from_str(from_utf8(&src[idxs[3] + 1..idxs[4]])?)?;
-        let ac_out_active_power = usize::from_str(from_utf8(&src[idxs[4] + 1..idxs[5]])?)?;
-        let out_load_percent = usize::from_str(from_utf8(&src[idxs[5] + 1..idxs[6]])?)?;
-        let bus_voltage = usize::from_str(from_utf8(&src[idxs[6] + 1..idxs[7]])?)?;
-        let battery_voltage = f32::from_str(from_utf8(&src[idxs[7] + 1..idxs[8]])?)?;
-        let battery_charge_current = usize::from_str(from_utf8(&src[idxs[8] + 1..idxs[9]])?)?;
-        let battery_capacity = usize::from_str(from_utf8(&src[idxs[9] + 1..idxs[10]])?)?;
-        let inverter_heat_sink_temp = usize::from_str(from_utf8(&src[idxs[10] + 1..idxs[11]])?)?;
-        let pv_input_current = usize::from_str(from_utf8(&src[idxs[11] + 1..idxs[12]])?)?;
-        let pv_input_voltage = f32::from_str(from_utf8(&src[idxs[12] + 1..idxs[13]])?)?;
-        let battery_scc_voltage = f32::from_str(from_utf8(&src[idxs[13] + 1..idxs[14]])?)?;
-        let battery_discharge_current = usize::from_str(from_utf8(&src[idxs[14] + 1..idxs[15]])?)?;
-        let device_status = &src[idxs[15] + 1..idxs[16]];
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl QPIGSResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 17..=23)?;
+
+        let grid_voltage = fields.parse("grid_voltage")?;
+        let grid_frequency = fields.parse("grid_frequency")?;
+        let ac_out_voltage = fields.parse("ac_out_voltage")?;
+        let ac_out_frequency = fields.parse("ac_out_frequency")?;
+        let ac_out_apparent_power = fields.parse("ac_out_apparent_power")?;
+        let ac_out_active_power = fields.parse("ac_out_active_power")?;
+        let out_load_percent = fields.parse("out_load_percent")?;
+        let bus_voltage = fields.parse("bus_voltage")?;
+        let battery_voltage = fields.parse("battery_voltage")?;
+        let battery_charge_current = fields.parse("battery_charge_current")?;
+        let battery_capacity = fields.parse("battery_capacity")?;
+        let inverter_heat_sink_temp = fields.parse("inverter_heat_sink_temp")?;
+        let pv_input_current = fields.parse("pv_input_current")?;
+        let pv_input_voltage = fields.parse("pv_input_voltage")?;
+        let battery_scc_voltage = fields.parse("battery_scc_voltage")?;
+        let battery_discharge_current = fields.parse("battery_discharge_current")?;
+        let device_status = fields.raw("device_status")?;
+        if device_status.len() != 8 {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidDeviceStatus,
+                "device_status",
+                device_status,
+            ));
+        }
+        let pv_charging_power = if fields.remaining() >= 3 {
+            fields.skip("battery_voltage_offset")?;
+            fields.skip("eeprom_version")?;
+            Some(fields.parse("pv_charging_power")?)
+        } else {
+            None
+        };
 
         Ok(Self {
             grid_voltage,
@@ -111,9 +127,17 @@ impl Response for QPIGSResponse {
                     "110" => ChargingFromSCC,
                     "101" => ChargingFromAC,
                     "111" => ChargingFromSCCAndAC,
-                    _ => return Err(Error::InvalidDeviceStatus),
+                    x if lenient => DeviceChargingStatus::Unknown(x.to_owned()),
+                    x => {
+                        return Err(Error::invalid_field(
+                            ErrorKind::InvalidDeviceStatus,
+                            "charge_status",
+                            x.as_bytes(),
+                        ))
+                    }
                 },
             },
+            pv_charging_power,
         })
     }
 }
@@ -126,7 +150,7 @@ mod test {
         ChargingFromAC, ChargingFromSCC, ChargingFromSCCAndAC, NotCharging,
     };
     use crate::commands::qpigs::{DeviceStatus, QPIGSResponse, QPIGS};
-    use crate::error::Result;
+    use crate::error::{ErrorKind, Result};
     use bytes::{Buf, BytesMut};
     use crc_any::CRCu16;
     use rand::{random, thread_rng, Rng};
@@ -143,8 +167,8 @@ mod test {
     fn test_qpigs_payload_decode_custom() -> Result<()> {
         let res = "001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0000 074.9 27.12 00005 10110110 17 04 00010 100";
 
-        let mut buf = BytesMut::from(res);
-        let item = <QPIGS as Command>::Response::decode(&mut buf)?;
+        let buf = BytesMut::from(res);
+        let item = <QPIGS as Command>::Response::decode(&buf)?;
         // println!("Test result: {:#?}", item);
         assert_eq!(
             item,
@@ -169,12 +193,40 @@ mod test {
                     active_load: true,
                     charge_status: ChargingFromSCC,
                 },
+                pv_charging_power: Some(10),
             }
         );
 
+        let item = <QPIGS as Command>::Response::decode(&res.as_bytes()[..96])?;
+        assert_eq!(item.pv_charging_power, None);
+
         Ok(())
     }
 
+    #[test]
+    fn test_qpigs_payload_decode_field_count() {
+        let res = "001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0000 074.9 27.12 00005 10110110 17 04 00010 100";
+
+        let err = <QPIGS as Command>::Response::decode(&res.as_bytes()[..45]).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 17,
+                actual: 9
+            }
+        ));
+
+        let long = format!("{} 0 0 0", res);
+        let err = <QPIGS as Command>::Response::decode(long.as_bytes()).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooManyFields {
+                expected: 23,
+                actual: 24
+            }
+        ));
+    }
+
     #[test]
     fn test_qpigs_command_encode() -> Result<()> {
         let mut codec = Codec::<QPIGS>::new();
@@ -268,7 +320,7 @@ mod test {
                     battery_scc_voltage,
                     battery_discharge_current,
                     device_status: DeviceStatus {
-                        active_load: active_load,
+                        active_load,
                         charge_status: match charge_status {
                             "000" => NotCharging,
                             "110" => ChargingFromSCC,
@@ -277,6 +329,7 @@ mod test {
                             _ => unreachable!(),
                         },
                     },
+                    pv_charging_power: Some(10),
                 })
             );
         }
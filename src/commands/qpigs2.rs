@@ -0,0 +1,103 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct QPIGS2;
+
+// Only dual-MPPT units answer this; the first PV input is part of `QPIGS`.
+impl Command for QPIGS2 {
+    const PROTOCOL_ID: &'static [u8] = b"QPIGS2";
+    const COMMAND_NAME: &'static str = "QueryDeviceGeneralStatus2";
+
+    type Request = ();
+    type Response = QPIGS2Response;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct QPIGS2Response {
+    pub pv_input_current: f32,
+    pub pv_input_voltage: f32,
+    pub pv_charging_power: usize,
+}
+
+impl Response for QPIGS2Response {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 3..=3)?;
+
+        Ok(Self {
+            pv_input_current: fields.parse("pv_input_current")?,
+            pv_input_voltage: fields.parse("pv_input_voltage")?,
+            pv_charging_power: fields.parse("pv_charging_power")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qpigs2::{QPIGS2Response, QPIGS2};
+    use crate::error::{ErrorKind, Result};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qpigs2_payload_encode() -> Result<()> {
+        let req: <QPIGS2 as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qpigs2_payload_decode() -> Result<()> {
+        let item = <QPIGS2 as Command>::Response::decode(b"03.1 327.3 01026")?;
+        assert_eq!(
+            item,
+            QPIGS2Response {
+                pv_input_current: 3.1,
+                pv_input_voltage: 327.3,
+                pv_charging_power: 1026,
+            }
+        );
+
+        let err = <QPIGS2 as Command>::Response::decode(b"03.1 327.3").unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 3,
+                actual: 2
+            }
+        ));
+        assert!(<QPIGS2 as Command>::Response::decode(b"03.1 327.3 -1026").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qpigs2_command_encode() -> Result<()> {
+        let mut codec = Codec::<QPIGS2>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QPIGS2\x68\x2d\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qpigs2_command_decode() -> Result<()> {
+        let mut codec = Codec::<QPIGS2>::new();
+
+        let mut buf = BytesMut::from(&b"(03.1 327.3 01026\xc5d\r"[..]);
+        let item = codec.decode(&mut buf)?.unwrap();
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(item.pv_charging_power, 1026);
+
+        Ok(())
+    }
+}
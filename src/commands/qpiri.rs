@@ -1,5 +1,7 @@
 use crate::command::{Command, Response};
-use crate::commands::qpiri::BatteryType::{Flooded, User, AGM};
+use crate::commands::qpiri::BatteryType::{
+    Flooded, LIb, LIc, Lithium, Pylontech, Soltaro, User, AGM, WECO,
+};
 use crate::commands::qpiri::ChargeSourcePriority::{
     GridFirst as ChargeSourceGridFirst, OnlySolar, SolarAndGrid,
     SolarFirst as ChargeSourceSolarFirst,
@@ -13,11 +15,11 @@ use crate::commands::qpiri::OutputSourcePriority::{
     GridFirst as OutputSourceGridFirst, SBUFirst, SolarFirst as OutputSourceSolarFirst,
 };
 use crate::commands::qpiri::Topology::{Transformer, Transformerless};
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
 use serde_derive::Serialize;
-use std::str::from_utf8;
-use std::str::FromStr;
 
 pub struct QPIRI;
 
@@ -55,94 +57,190 @@ pub struct QPIRIResponse {
     pub battery_redischarge_voltage: f32,
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+// The `Unknown` variants hold codes kept as sent by `Response::decode_lenient`.
+
+// Codes 3 and up are the lithium profiles of newer PI30 firmware, also reported by PI18 `PIRI`:
+// 3 generic lithium, 4 Pylontech, 5 WECO, 6 Soltaro, 8 LIb and 9 LIc, with 7 unassigned. The
+// older protocol documents only list 0 to 2, and some number the brand profiles differently, so
+// anything outside this table is an error, or `Unknown` when decoding leniently.
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum BatteryType {
     AGM,
     Flooded,
     User,
+    Lithium,
+    Pylontech,
+    WECO,
+    Soltaro,
+    // Lithium pack speaking a protocol compatible with the `LIb` setting.
+    LIb,
+    // Third party lithium pack.
+    LIc,
+    Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+impl BatteryType {
+    /// The numeric code used by `QPIRI` and `PBT`.
+    pub fn code(&self) -> Option<u8> {
+        Some(match self {
+            Self::AGM => 0,
+            Self::Flooded => 1,
+            Self::User => 2,
+            Self::Lithium => 3,
+            Self::Pylontech => 4,
+            Self::WECO => 5,
+            Self::Soltaro => 6,
+            Self::LIb => 8,
+            Self::LIc => 9,
+            Self::Unknown(_) => return None,
+        })
+    }
+
+    pub(crate) fn decode(code: &str, lenient: bool) -> Result<Self> {
+        Ok(match code {
+            "0" => AGM,
+            "1" => Flooded,
+            "2" => User,
+            "3" => Lithium,
+            "4" => Pylontech,
+            "5" => WECO,
+            "6" => Soltaro,
+            "8" => LIb,
+            "9" => LIc,
+            x if lenient => Self::Unknown(x.to_owned()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceBatteryType,
+                    "battery_type",
+                    x.as_bytes(),
+                ))
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum InputVoltageRange {
     Appliance,
     UPS,
+    Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+impl InputVoltageRange {
+    /// The numeric code used by `QPIRI` and `PGR`.
+    pub fn code(&self) -> Option<u8> {
+        match self {
+            Self::Appliance => Some(0),
+            Self::UPS => Some(1),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum OutputSourcePriority {
     GridFirst,
     SolarFirst,
     SBUFirst,
+    Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum ChargeSourcePriority {
     GridFirst,
     SolarFirst,
     SolarAndGrid,
     OnlySolar,
+    Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum MachineType {
     GridTie,
     OffGrid,
     Hybrid,
+    Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum Topology {
     Transformerless,
     Transformer,
+    Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum OutputMode {
     SingleMachineOutput,
     ParallelOutput,
     Phase1Of3Output,
     Phase2Of3Output,
     Phase3Of3Output,
+    Unknown(String),
+}
+
+impl OutputMode {
+    /// Decode the numeric code used by `QOPM` and `POPM`.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::SingleMachineOutput,
+            1 => Self::ParallelOutput,
+            2 => Self::Phase1Of3Output,
+            3 => Self::Phase2Of3Output,
+            4 => Self::Phase3Of3Output,
+            _ => return None,
+        })
+    }
+
+    pub fn code(&self) -> Option<u8> {
+        Some(match self {
+            Self::SingleMachineOutput => 0,
+            Self::ParallelOutput => 1,
+            Self::Phase1Of3Output => 2,
+            Self::Phase2Of3Output => 3,
+            Self::Phase3Of3Output => 4,
+            Self::Unknown(_) => return None,
+        })
+    }
 }
 
 impl Response for QPIRIResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
-        // println!("Input: {:?}", from_utf8(&src)?);
-
-        // Extract indices
-        let mut idxs = [0usize; 24];
-        src.iter()
-            .cloned()
-            .enumerate()
-            .filter(|(_, x)| *x == ' ' as u8)
-            .fold(0, |num_idx, byte_idx| {
-                idxs[num_idx] = byte_idx.0;
-                num_idx + 1
-            });
-
-        // Extract data
-        let grid_rating_voltage = f32::from_str(from_utf8(&src[0..idxs[0]])?)?;
-        let grid_rating_current = f32::from_str(from_utf8(&src[idxs[0] + 1..idxs[1]])?)?;
-        let ac_output_rating_voltage = f32::from_str(from_utf8(&src[idxs[1] + 1..idxs[2]])?)?;
-        let ac_out_rating_frequency = f32::from_str(from_utf8(&src[idxs[2] + 1..idxs[3]])?)?;
-        let ac_out_rating_current = f32::from_str(from_utf8(&src[idxs[3] + 1..idxs[4]])?)?;
-        let ac_out_rating_apparent_power = i32::from_str(from_utf8(&src[idxs[4] + 1..idxs[5]])?)?;
-        let ac_out_rating_active_power = i32::from_str(from_utf8(&src[idxs[5] + 1..idxs[6]])?)?;
-        let battery_rating_voltage = f32::from_str(from_utf8(&src[idxs[6] + 1..idxs[7]])?)?;
-        let battery_recharge_voltage = f32::from_str(from_utf8(&src[idxs[7] + 1..idxs[8]])?)?;
-        let battery_under_voltage = f32::from_str(from_utf8(&src[idxs[8] + 1..idxs[9]])?)?;
-        let battery_bulk_voltage = f32::from_str(from_utf8(&src[idxs[9] + 1..idxs[10]])?)?;
-        let battery_float_voltage = f32::from_str(from_utf8(&src[idxs[10] + 1..idxs[11]])?)?;
-        let battery_type = from_utf8(&src[idxs[11] + 1..idxs[12]])?;
-        let max_ac_charging_current = i32::from_str(from_utf8(&src[idxs[12] + 1..idxs[13]])?)?;
-        let max_charging_current = i32::from_str(from_utf8(&src[idxs[13] + 1..idxs[14]])?)?;
-        let input_voltage_range = from_utf8(&src[idxs[14] + 1..idxs[15]])?;
-        let output_source_priority = from_utf8(&src[idxs[15] + 1..idxs[16]])?;
-        let charge_source_priority = from_utf8(&src[idxs[16] + 1..idxs[17]])?;
-        let machine_type = from_utf8(&src[idxs[18] + 1..idxs[19]])?;
-        let topology = from_utf8(&src[idxs[19] + 1..idxs[20]])?;
-        let output_mode = from_utf8(&src[idxs[20] + 1..idxs[21]])?;
-        let battery_redischarge_voltage = f32::from_str(from_utf8(&src[idxs[21] + 1..idxs[22]])?)?;
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl QPIRIResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 23..=25)?;
+
+        let grid_rating_voltage = fields.parse("grid_rating_voltage")?;
+        let grid_rating_current = fields.parse("grid_rating_current")?;
+        let ac_output_rating_voltage = fields.parse("ac_output_rating_voltage")?;
+        let ac_out_rating_frequency = fields.parse("ac_out_rating_frequency")?;
+        let ac_out_rating_current = fields.parse("ac_out_rating_current")?;
+        let ac_out_rating_apparent_power = fields.parse("ac_out_rating_apparent_power")?;
+        let ac_out_rating_active_power = fields.parse("ac_out_rating_active_power")?;
+        let battery_rating_voltage = fields.parse("battery_rating_voltage")?;
+        let battery_recharge_voltage = fields.parse("battery_recharge_voltage")?;
+        let battery_under_voltage = fields.parse("battery_under_voltage")?;
+        let battery_bulk_voltage = fields.parse("battery_bulk_voltage")?;
+        let battery_float_voltage = fields.parse("battery_float_voltage")?;
+        let battery_type = fields.str("battery_type")?;
+        let max_ac_charging_current = fields.parse("max_ac_charging_current")?;
+        let max_charging_current = fields.parse("max_charging_current")?;
+        let input_voltage_range = fields.str("input_voltage_range")?;
+        let output_source_priority = fields.str("output_source_priority")?;
+        let charge_source_priority = fields.str("charge_source_priority")?;
+        fields.skip("parallel_max_num")?;
+        let machine_type = fields.str("machine_type")?;
+        let topology = fields.str("topology")?;
+        let output_mode = fields.str("output_mode")?;
+        let battery_redischarge_voltage = fields.parse("battery_redischarge_voltage")?;
 
         Ok(Self {
             grid_rating_voltage,
@@ -157,42 +255,72 @@ impl Response for QPIRIResponse {
             battery_under_voltage,
             battery_bulk_voltage,
             battery_float_voltage,
-            battery_type: match battery_type {
-                "0" => AGM,
-                "1" => Flooded,
-                "2" => User,
-                _ => return Err(Error::InvalidDeviceBatteryType),
-            },
+            battery_type: BatteryType::decode(battery_type, lenient)?,
             max_ac_charging_current,
             max_charging_current,
             input_voltage_range: match input_voltage_range {
                 "0" => Appliance,
                 "1" => UPS,
-                _ => return Err(Error::InvalidDeviceInputVoltageRange),
+                x if lenient => InputVoltageRange::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceInputVoltageRange,
+                        "input_voltage_range",
+                        x.as_bytes(),
+                    ))
+                }
             },
             output_source_priority: match output_source_priority {
                 "0" => OutputSourceGridFirst,
                 "1" => OutputSourceSolarFirst,
                 "2" => SBUFirst,
-                _ => return Err(Error::InvalidDeviceOutputSourcePriority),
+                x if lenient => OutputSourcePriority::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceOutputSourcePriority,
+                        "output_source_priority",
+                        x.as_bytes(),
+                    ))
+                }
             },
             charge_source_priority: match charge_source_priority {
                 "0" => ChargeSourceGridFirst,
                 "1" => ChargeSourceSolarFirst,
                 "2" => SolarAndGrid,
                 "3" => OnlySolar,
-                _ => return Err(Error::InvalidDeviceChargeSourcePriority),
+                x if lenient => ChargeSourcePriority::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceChargeSourcePriority,
+                        "charge_source_priority",
+                        x.as_bytes(),
+                    ))
+                }
             },
             machine_type: match machine_type {
                 "00" => GridTie,
                 "01" => OffGrid,
                 "10" => Hybrid,
-                _ => return Err(Error::InvalidDeviceMachineType),
+                x if lenient => MachineType::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceMachineType,
+                        "machine_type",
+                        x.as_bytes(),
+                    ))
+                }
             },
             topology: match topology {
                 "0" => Transformerless,
                 "1" => Transformer,
-                _ => return Err(Error::InvalidDeviceTopology),
+                x if lenient => Topology::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceTopology,
+                        "topology",
+                        x.as_bytes(),
+                    ))
+                }
             },
             output_mode: match output_mode {
                 "0" => SingleMachineOutput,
@@ -200,7 +328,14 @@ impl Response for QPIRIResponse {
                 "2" => Phase1Of3Output,
                 "3" => Phase2Of3Output,
                 "4" => Phase3Of3Output,
-                _ => return Err(Error::InvalidDeviceOutputMode),
+                x if lenient => OutputMode::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceOutputMode,
+                        "output_mode",
+                        x.as_bytes(),
+                    ))
+                }
             },
             battery_redischarge_voltage,
         })
@@ -229,7 +364,7 @@ mod test {
         BatteryType, ChargeSourcePriority, InputVoltageRange, MachineType, OutputMode,
         OutputSourcePriority, QPIRIResponse, Topology, QPIRI,
     };
-    use crate::error::Result;
+    use crate::error::{ErrorKind, Result};
     use bytes::{Buf, BytesMut};
     use crc_any::CRCu16;
     use rand::{thread_rng, Rng};
@@ -246,8 +381,8 @@ mod test {
     fn test_qpiri_payload_decode_custom() -> Result<()> {
         let res = "230.0 13.0 230.0 50.0 13.0 3000 2400 24.0 23.0 21.0 28.2 27.0 0 30 60 0 0 0 - 01 1 0 27.0 0 0";
 
-        let mut buf = BytesMut::from(res);
-        let item = <QPIRI as Command>::Response::decode(&mut buf)?;
+        let buf = BytesMut::from(res);
+        let item = <QPIRI as Command>::Response::decode(&buf)?;
         // println!("Test result: {:#?}", item);
         assert_eq!(
             item,
@@ -280,6 +415,41 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_qpiri_payload_decode_field_count() {
+        let err = <QPIRI as Command>::Response::decode(b"230.0 13.0 230.0").unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 23,
+                actual: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn test_qpiri_payload_decode_battery_type() -> Result<()> {
+        let res = |battery_type: &str| {
+            format!(
+                "230.0 13.0 230.0 50.0 13.0 3000 2400 24.0 23.0 21.0 28.2 27.0 {} 30 60 0 0 0 - 01 1 0 27.0 0 0",
+                battery_type
+            )
+        };
+
+        let item = <QPIRI as Command>::Response::decode(res("4").as_bytes())?;
+        assert_eq!(item.battery_type, BatteryType::Pylontech);
+        assert_eq!(item.battery_type.code(), Some(4));
+
+        let err = <QPIRI as Command>::Response::decode(res("7").as_bytes()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidDeviceBatteryType));
+
+        let item = <QPIRI as Command>::Response::decode_lenient(res("7").as_bytes())?;
+        assert_eq!(item.battery_type, BatteryType::Unknown(String::from("7")));
+        assert_eq!(item.battery_type.code(), None);
+
+        Ok(())
+    }
+
     #[test]
     fn test_qpiri_command_encode() -> Result<()> {
         let mut codec = Codec::<QPIRI>::new();
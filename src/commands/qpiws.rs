@@ -1,8 +1,11 @@
 use crate::command::{Command, Response};
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::{Error, ErrorKind, Result};
+use crate::status::{Severity, Warning, WarningCategory};
+use crate::tokenizer::check_field_count;
+use alloc::string::String;
+use core::fmt::{self, Display};
+use core::ops::{BitAnd, BitOr};
 use serde_derive::Serialize;
-use std::str::from_utf8;
 
 pub struct QPIWS;
 
@@ -43,58 +46,330 @@ pub struct QPIWSResponse {
     pub mppt_overload_fault: bool,
     pub mppt_overload_warning: bool,
     pub battery_too_low_to_charge: bool,
+    // Flags at positions without a name, kept as sent.
+    pub reserved: WarningSet,
 }
 
 impl QPIWSResponse {
-    fn decode_warning(src: &mut BytesMut, position: usize) -> Result<bool> {
-        Ok(match from_utf8(&src[position..(position + 1)])? {
-            "0" => false,
-            "1" => true,
-            _ => return Err(Error::InvalidWarningStatus),
-        })
+    fn decode_warning(src: &[u8], position: usize, field: &'static str) -> Result<bool> {
+        match src[position] {
+            b'0' => Ok(false),
+            b'1' => Ok(true),
+            _ => Err(Error::invalid_field(
+                ErrorKind::InvalidWarningStatus,
+                field,
+                &src[position..=position],
+            )),
+        }
+    }
+}
+
+impl QPIWSResponse {
+    pub fn warning_set(&self) -> WarningSet {
+        NAMED_POSITIONS
+            .iter()
+            .zip(self.flags().iter())
+            .filter(|(_, active)| **active)
+            .fold(self.reserved, |mut set, (&(position, _), _)| {
+                set.insert(position);
+                set
+            })
+    }
+
+    fn flags(&self) -> [bool; 27] {
+        [
+            self.inverter_fault,
+            self.bus_over,
+            self.bus_under,
+            self.bus_soft_fail,
+            self.line_fail,
+            self.opv_short,
+            self.inverter_voltage_too_low,
+            self.inverter_voltage_too_high,
+            self.over_temperature,
+            self.fan_locked,
+            self.battery_voltage_high,
+            self.battery_low_alarm,
+            self.battery_under_shutdown,
+            self.over_load,
+            self.eeprom_fault,
+            self.inverter_over_current,
+            self.inverter_soft_fail,
+            self.self_test_fail,
+            self.op_dc_voltage_over,
+            self.bat_open,
+            self.current_sensor_fail,
+            self.battery_short,
+            self.power_limit,
+            self.pv_voltage_high,
+            self.mppt_overload_fault,
+            self.mppt_overload_warning,
+            self.battery_too_low_to_charge,
+        ]
     }
 }
 
 impl Response for QPIWSResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
+    fn decode(src: &[u8]) -> Result<Self> {
+        // Every character is a field.
+        check_field_count(src.len(), WarningSet::WIRE_LEN..=WarningSet::WIRE_LEN)?;
+
         Ok(Self {
-            inverter_fault: Self::decode_warning(src, 1)?,
-            bus_over: Self::decode_warning(src, 2)?,
-            bus_under: Self::decode_warning(src, 3)?,
-            bus_soft_fail: Self::decode_warning(src, 4)?,
-            line_fail: Self::decode_warning(src, 5)?,
-            opv_short: Self::decode_warning(src, 6)?,
-            inverter_voltage_too_low: Self::decode_warning(src, 7)?,
-            inverter_voltage_too_high: Self::decode_warning(src, 8)?,
-            over_temperature: Self::decode_warning(src, 9)?,
-            fan_locked: Self::decode_warning(src, 10)?,
-            battery_voltage_high: Self::decode_warning(src, 11)?,
-            battery_low_alarm: Self::decode_warning(src, 12)?,
-            battery_under_shutdown: Self::decode_warning(src, 14)?,
-            over_load: Self::decode_warning(src, 16)?,
-            eeprom_fault: Self::decode_warning(src, 17)?,
-            inverter_over_current: Self::decode_warning(src, 18)?,
-            inverter_soft_fail: Self::decode_warning(src, 19)?,
-            self_test_fail: Self::decode_warning(src, 20)?,
-            op_dc_voltage_over: Self::decode_warning(src, 21)?,
-            bat_open: Self::decode_warning(src, 22)?,
-            current_sensor_fail: Self::decode_warning(src, 23)?,
-            battery_short: Self::decode_warning(src, 24)?,
-            power_limit: Self::decode_warning(src, 25)?,
-            pv_voltage_high: Self::decode_warning(src, 26)?,
-            mppt_overload_fault: Self::decode_warning(src, 27)?,
-            mppt_overload_warning: Self::decode_warning(src, 28)?,
-            battery_too_low_to_charge: Self::decode_warning(src, 29)?,
+            inverter_fault: Self::decode_warning(src, 1, "inverter_fault")?,
+            bus_over: Self::decode_warning(src, 2, "bus_over")?,
+            bus_under: Self::decode_warning(src, 3, "bus_under")?,
+            bus_soft_fail: Self::decode_warning(src, 4, "bus_soft_fail")?,
+            line_fail: Self::decode_warning(src, 5, "line_fail")?,
+            opv_short: Self::decode_warning(src, 6, "opv_short")?,
+            inverter_voltage_too_low: Self::decode_warning(src, 7, "inverter_voltage_too_low")?,
+            inverter_voltage_too_high: Self::decode_warning(src, 8, "inverter_voltage_too_high")?,
+            over_temperature: Self::decode_warning(src, 9, "over_temperature")?,
+            fan_locked: Self::decode_warning(src, 10, "fan_locked")?,
+            battery_voltage_high: Self::decode_warning(src, 11, "battery_voltage_high")?,
+            battery_low_alarm: Self::decode_warning(src, 12, "battery_low_alarm")?,
+            battery_under_shutdown: Self::decode_warning(src, 14, "battery_under_shutdown")?,
+            over_load: Self::decode_warning(src, 16, "over_load")?,
+            eeprom_fault: Self::decode_warning(src, 17, "eeprom_fault")?,
+            inverter_over_current: Self::decode_warning(src, 18, "inverter_over_current")?,
+            inverter_soft_fail: Self::decode_warning(src, 19, "inverter_soft_fail")?,
+            self_test_fail: Self::decode_warning(src, 20, "self_test_fail")?,
+            op_dc_voltage_over: Self::decode_warning(src, 21, "op_dc_voltage_over")?,
+            bat_open: Self::decode_warning(src, 22, "bat_open")?,
+            current_sensor_fail: Self::decode_warning(src, 23, "current_sensor_fail")?,
+            battery_short: Self::decode_warning(src, 24, "battery_short")?,
+            power_limit: Self::decode_warning(src, 25, "power_limit")?,
+            pv_voltage_high: Self::decode_warning(src, 26, "pv_voltage_high")?,
+            mppt_overload_fault: Self::decode_warning(src, 27, "mppt_overload_fault")?,
+            mppt_overload_warning: Self::decode_warning(src, 28, "mppt_overload_warning")?,
+            battery_too_low_to_charge: Self::decode_warning(src, 29, "battery_too_low_to_charge")?,
+            reserved: WarningSet::decode(src)?.unknown(),
         })
     }
 }
 
+// Wire positions of the named flags, in `QPIWSResponse` field order.
+const NAMED_POSITIONS: [(u8, Warning); 27] = [
+    (1, Warning::InverterFault),
+    (2, Warning::BusOver),
+    (3, Warning::BusUnder),
+    (4, Warning::BusSoftFail),
+    (5, Warning::LineFail),
+    (6, Warning::OpvShort),
+    (7, Warning::InverterVoltageTooLow),
+    (8, Warning::InverterVoltageTooHigh),
+    (9, Warning::OverTemperature),
+    (10, Warning::FanLocked),
+    (11, Warning::BatteryVoltageHigh),
+    (12, Warning::BatteryLowAlarm),
+    (14, Warning::BatteryUnderShutdown),
+    (16, Warning::OverLoad),
+    (17, Warning::EepromFault),
+    (18, Warning::InverterOverCurrent),
+    (19, Warning::InverterSoftFail),
+    (20, Warning::SelfTestFail),
+    (21, Warning::OpDcVoltageOver),
+    (22, Warning::BatteryOpen),
+    (23, Warning::CurrentSensorFail),
+    (24, Warning::BatteryShort),
+    (25, Warning::PowerLimit),
+    (26, Warning::PvVoltageHigh),
+    (27, Warning::MpptOverloadFault),
+    (28, Warning::MpptOverloadWarning),
+    (29, Warning::BatteryTooLowToCharge),
+];
+
+/// QPIWS flags as a bit set, where bit `n` is the flag at position `n` of the wire string.
+///
+/// Positions 0, 13, 15, 30 and 31 have no name; some firmwares use them (e.g. battery weak or
+/// battery equalization), so they are kept as unknown flags.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub struct WarningSet(u32);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum WarningFlag {
+    Known(Warning),
+    Unknown(u8),
+}
+
+impl WarningFlag {
+    /// Wire position of the flag, `None` for warnings that only other protocols report.
+    pub fn position(&self) -> Option<u8> {
+        match self {
+            Self::Known(warning) => WarningSet::position_of(*warning),
+            Self::Unknown(position) => Some(*position),
+        }
+    }
+
+    pub fn category(&self) -> Option<WarningCategory> {
+        match self {
+            Self::Known(warning) => Some(warning.category()),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl WarningSet {
+    pub const WIRE_LEN: usize = 32;
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn contains(&self, position: u8) -> bool {
+        position < 32 && self.0 & (1 << position) != 0
+    }
+
+    /// Positions past the 32 flags are ignored, as `contains` never reports them.
+    pub fn insert(&mut self, position: u8) {
+        if position < 32 {
+            self.0 |= 1 << position;
+        }
+    }
+
+    pub fn remove(&mut self, position: u8) {
+        if position < 32 {
+            self.0 &= !(1 << position);
+        }
+    }
+
+    /// Wire position of a PI30 warning, if it has one.
+    pub fn position_of(warning: Warning) -> Option<u8> {
+        NAMED_POSITIONS
+            .iter()
+            .find(|(_, x)| *x == warning)
+            .map(|(position, _)| *position)
+    }
+
+    pub fn contains_warning(&self, warning: Warning) -> bool {
+        Self::position_of(warning).map_or(false, |position| self.contains(position))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = WarningFlag> + '_ {
+        (0..32u8)
+            .filter(move |&x| self.contains(x))
+            .map(
+                |position| match NAMED_POSITIONS.iter().find(|(x, _)| *x == position) {
+                    Some((_, warning)) => WarningFlag::Known(*warning),
+                    None => WarningFlag::Unknown(position),
+                },
+            )
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = Warning> + '_ {
+        self.iter().filter_map(|flag| match flag {
+            WarningFlag::Known(warning) => Some(warning),
+            WarningFlag::Unknown(_) => None,
+        })
+    }
+
+    /// Flags at positions without a name.
+    pub fn unknown(&self) -> Self {
+        self.filter(|flag| matches!(flag, WarningFlag::Unknown(_)))
+    }
+
+    /// Severity of a flag within this set, following the PI30 table. Unknown flags are warnings.
+    pub fn severity(&self, flag: WarningFlag) -> Severity {
+        match flag {
+            WarningFlag::Known(warning) => {
+                warning.severity(self.contains_warning(Warning::InverterFault))
+            }
+            WarningFlag::Unknown(_) => Severity::Warning,
+        }
+    }
+
+    pub fn filter_severity(&self, severity: Severity) -> Self {
+        self.filter(|flag| self.severity(flag) == severity)
+    }
+
+    pub fn filter_category(&self, category: WarningCategory) -> Self {
+        self.filter(|flag| flag.category() == Some(category))
+    }
+
+    fn filter(&self, predicate: impl Fn(WarningFlag) -> bool) -> Self {
+        self.iter()
+            .filter(|&flag| predicate(flag))
+            .filter_map(|flag| flag.position())
+            .fold(Self::empty(), |mut set, position| {
+                set.insert(position);
+                set
+            })
+    }
+
+    /// Decode the wire string, which has to be exactly 32 characters like the `QPIWS` payload.
+    pub fn decode(src: &[u8]) -> Result<Self> {
+        check_field_count(src.len(), Self::WIRE_LEN..=Self::WIRE_LEN)?;
+
+        src.iter()
+            .enumerate()
+            .try_fold(Self::empty(), |mut set, (position, x)| {
+                match x {
+                    b'0' => {}
+                    b'1' => set.insert(position as u8),
+                    _ => {
+                        return Err(Error::invalid_field(
+                            ErrorKind::InvalidWarningStatus,
+                            "warning_status",
+                            src,
+                        ))
+                    }
+                }
+
+                Ok(set)
+            })
+    }
+
+    pub fn to_wire_string(&self) -> String {
+        (0..Self::WIRE_LEN as u8)
+            .map(|x| if self.contains(x) { '1' } else { '0' })
+            .collect()
+    }
+}
+
+impl Display for WarningSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_wire_string())
+    }
+}
+
+impl BitOr for WarningSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for WarningSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::codec::Codec;
     use crate::command::{Command, Request, Response};
-    use crate::commands::qpiws::{QPIWSResponse, QPIWS};
-    use crate::error::Result;
+    use crate::commands::qpiws::{QPIWSResponse, WarningFlag, WarningSet, QPIWS};
+    use crate::error::{ErrorKind, Result};
+    use crate::status::{Severity, Warning, WarningCategory};
     use bytes::{Buf, BytesMut};
     use crc_any::CRCu16;
     use rand::{thread_rng, Rng};
@@ -142,7 +417,7 @@ mod test {
             let mppt_overload_warning = rng.gen_bool(0.5f64);
             let battery_too_low_to_charge = rng.gen_bool(0.5f64);
 
-            let mut buf = BytesMut::from(
+            let buf = BytesMut::from(
                 format!(
                     "0{}{}{}{}{}{}{}{}{}{}{}{}0{}0{}{}{}{}{}{}{}{}{}{}{}{}{}{}00",
                     inverter_fault as i32,
@@ -176,7 +451,7 @@ mod test {
                 .into_bytes()
                 .as_slice(),
             );
-            let item = <QPIWS as Command>::Response::decode(&mut buf)?;
+            let item = <QPIWS as Command>::Response::decode(&buf)?;
 
             assert_eq!(
                 item,
@@ -207,7 +482,8 @@ mod test {
                     pv_voltage_high,
                     mppt_overload_fault,
                     mppt_overload_warning,
-                    battery_too_low_to_charge
+                    battery_too_low_to_charge,
+                    reserved: WarningSet::empty(),
                 }
             );
         }
@@ -215,6 +491,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_qpiws_payload_decode_length() {
+        let err = <QPIWS as Command>::Response::decode(b"0100").unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 32,
+                actual: 4
+            }
+        ));
+
+        let err = <QPIWS as Command>::Response::decode(&[b'0'; 36]).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooManyFields {
+                expected: 32,
+                actual: 36
+            }
+        ));
+    }
+
     #[test]
     fn test_qpiws_command_encode() -> Result<()> {
         let mut codec = Codec::<QPIWS>::new();
@@ -331,11 +628,66 @@ mod test {
                     pv_voltage_high,
                     mppt_overload_fault,
                     mppt_overload_warning,
-                    battery_too_low_to_charge
+                    battery_too_low_to_charge,
+                    reserved: WarningSet::empty(),
                 })
             );
         }
 
         Ok(())
     }
+
+    #[test]
+    fn test_qpiws_warning_set() -> Result<()> {
+        // Inverter fault, over temperature, line fail and the unnamed positions 0 and 31.
+        let wire = "11000100010000000000000000000001";
+        let item = QPIWSResponse::decode(&BytesMut::from(wire))?;
+
+        assert_eq!(item.reserved, WarningSet::from_bits(1 | 1 << 31));
+        let set = item.warning_set();
+        assert_eq!(set.to_wire_string(), wire);
+        assert_eq!(set.len(), 5);
+        assert!(set.contains_warning(Warning::OverTemperature));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![
+                WarningFlag::Unknown(0),
+                WarningFlag::Known(Warning::InverterFault),
+                WarningFlag::Known(Warning::LineFail),
+                WarningFlag::Known(Warning::OverTemperature),
+                WarningFlag::Unknown(31),
+            ]
+        );
+        assert_eq!(
+            set.filter_severity(Severity::Fault)
+                .warnings()
+                .collect::<Vec<_>>(),
+            vec![Warning::InverterFault, Warning::OverTemperature]
+        );
+        assert_eq!(
+            set.filter_category(WarningCategory::Grid)
+                .warnings()
+                .collect::<Vec<_>>(),
+            vec![Warning::LineFail]
+        );
+        assert_eq!(WarningSet::decode(wire.as_bytes())?, set);
+        assert!(WarningSet::decode(b"0x").is_err());
+        assert!(WarningSet::decode(format!("{}0", wire).as_bytes()).is_err());
+
+        let mut set = WarningSet::empty();
+        set.insert(32);
+        assert!(set.is_empty());
+        set.insert(0);
+        set.remove(32);
+        assert_eq!(set, WarningSet::from_bits(1));
+        assert!(!set.contains(32));
+
+        // PI17 and PI18 warnings have no place in the PI30 wire string.
+        assert_eq!(WarningFlag::Known(Warning::PvInputLoss).position(), None);
+        assert_eq!(WarningFlag::Known(Warning::LineFail).position(), Some(5));
+        assert_eq!(WarningFlag::Unknown(31).position(), Some(31));
+        assert!(!set.contains_warning(Warning::EmergencyPowerOff));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,93 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::pi18::commands::id::IDResponse;
+use alloc::string::String;
+use serde_derive::Serialize;
+
+pub struct QSID;
+
+impl Command for QSID {
+    const PROTOCOL_ID: &'static [u8] = b"QSID";
+    const COMMAND_NAME: &'static str = "QuerySerialNumberLong";
+
+    type Request = ();
+    type Response = QSIDResponse;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QSIDResponse {
+    pub serial_number: String,
+}
+
+impl Response for QSIDResponse {
+    // Same layout as the PI18 `ID` response: the length of the serial number, the serial number
+    // and padding.
+    fn decode(src: &[u8]) -> Result<Self> {
+        Ok(Self {
+            serial_number: IDResponse::decode(src)?.serial_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qsid::{QSIDResponse, QSID};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qsid_payload_encode() -> Result<()> {
+        let req: <QSID as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qsid_payload_decode() -> Result<()> {
+        let item = <QSID as Command>::Response::decode(b"1492932105105335005535")?;
+        assert_eq!(
+            item,
+            QSIDResponse {
+                serial_number: String::from("92932105105335")
+            }
+        );
+
+        let item = <QSID as Command>::Response::decode(b"20AB0C0012345678900000000")?;
+        assert_eq!(item.serial_number, "AB0C0012345678900000");
+
+        assert!(<QSID as Command>::Response::decode(b"2012345").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qsid_command_encode() -> Result<()> {
+        let mut codec = Codec::<QSID>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QSID\xbb\x05\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qsid_command_decode() -> Result<()> {
+        let mut codec = Codec::<QSID>::new();
+
+        let mut buf = BytesMut::from(frame(b"1492932105105335005535").as_slice());
+        let item = codec.decode(&mut buf)?;
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(item.unwrap().serial_number, "92932105105335");
+
+        Ok(())
+    }
+}
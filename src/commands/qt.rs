@@ -0,0 +1,97 @@
+use crate::command::{Command, Response};
+use crate::datetime::DateTime;
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct QT;
+
+impl Command for QT {
+    const PROTOCOL_ID: &'static [u8] = b"QT";
+    const COMMAND_NAME: &'static str = "QueryTime";
+
+    type Request = ();
+    type Response = QTResponse;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QTResponse {
+    pub datetime: DateTime,
+}
+
+impl Response for QTResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b' ', 1..=1)?;
+
+        Ok(Self {
+            datetime: DateTime::parse(fields.str("datetime")?, "datetime")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qt::QT;
+    use crate::datetime::DateTime;
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qt_payload_encode() -> Result<()> {
+        let req: <QT as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qt_payload_decode() -> Result<()> {
+        let item = <QT as Command>::Response::decode(b"20201018123005")?;
+        assert_eq!(
+            item.datetime,
+            DateTime {
+                year: 2020,
+                month: 10,
+                day: 18,
+                hour: 12,
+                minute: 30,
+                second: 5,
+            }
+        );
+
+        assert!(<QT as Command>::Response::decode(b"20201318123005").is_err());
+        assert!(<QT as Command>::Response::decode(b"201018123005").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qt_command_encode() -> Result<()> {
+        let mut codec = Codec::<QT>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QT\x27\xff\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qt_command_decode() -> Result<()> {
+        let mut codec = Codec::<QT>::new();
+
+        let mut buf = BytesMut::from(frame(b"20201018123005").as_slice());
+        let item = codec.decode(&mut buf)?.unwrap();
+
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(item.datetime.to_string(), "2020-10-18 12:30:05");
+
+        Ok(())
+    }
+}
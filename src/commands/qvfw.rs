@@ -1,6 +1,6 @@
 use crate::command::{Command, Response};
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::Result;
+use crate::firmware::FirmwareVersion;
 use serde_derive::Serialize;
 
 pub struct QVFW;
@@ -13,40 +13,15 @@ impl Command for QVFW {
     type Response = QVFWResponse;
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
 pub struct QVFWResponse {
-    pub major: u64,
-    pub minor: u64,
+    pub firmware_version: FirmwareVersion,
 }
 
 impl Response for QVFWResponse {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
-        if !src.starts_with(b"VERFW:") {
-            return Err(Error::InvalidPayload(None));
-        }
-
-        let src = src.split_off(6);
-
-        let idx = src
-            .iter()
-            .copied()
-            .enumerate()
-            .find(|(_, x)| *x == '.' as u8)
-            .map(|(i, _)| i);
-        let idx = if let Some(x) = idx {
-            x
-        } else {
-            return Err(Error::InvalidPayload(None));
-        };
-
-        let (version_major, version_minor) = src.split_at(idx);
-
-        let version_major = u64::from_str_radix(std::str::from_utf8(version_major)?, 16)?;
-        let version_minor = u64::from_str_radix(std::str::from_utf8(&version_minor[1..])?, 16)?;
-
+    fn decode(src: &[u8]) -> Result<Self> {
         Ok(Self {
-            major: version_major,
-            minor: version_minor,
+            firmware_version: FirmwareVersion::decode_prefixed(src, b"VERFW:")?,
         })
     }
 }
@@ -57,6 +32,7 @@ mod test {
     use crate::command::{Command, Request, Response};
     use crate::commands::qvfw::{QVFWResponse, QVFW};
     use crate::error::Result;
+    use crate::firmware::FirmwareVersion;
     use bytes::{Buf, BytesMut};
     use crc_any::CRCu16;
     use rand::prelude::*;
@@ -79,14 +55,16 @@ mod test {
 
             let res = format!("VERFW:{:X}.{:X}", n_maj, n_min);
 
-            let mut buf = BytesMut::from(res.as_str());
-            let item = <QVFW as Command>::Response::decode(&mut buf)?;
+            let buf = BytesMut::from(res.as_str());
+            let item = <QVFW as Command>::Response::decode(&buf)?;
 
             assert_eq!(
                 item,
                 QVFWResponse {
-                    major: n_maj,
-                    minor: n_min
+                    firmware_version: FirmwareVersion {
+                        major: n_maj,
+                        minor: n_min
+                    }
                 }
             );
         }
@@ -127,8 +105,10 @@ mod test {
             assert_eq!(
                 item,
                 Some(QVFWResponse {
-                    major: n_maj,
-                    minor: n_min
+                    firmware_version: FirmwareVersion {
+                        major: n_maj,
+                        minor: n_min
+                    }
                 })
             );
         }
@@ -1,6 +1,6 @@
 use crate::command::{Command, Response};
-use crate::error::{Error, Result};
-use bytes::BytesMut;
+use crate::error::Result;
+use crate::firmware::FirmwareVersion;
 use serde_derive::Serialize;
 
 pub struct QVFW2;
@@ -13,40 +13,15 @@ impl Command for QVFW2 {
     type Response = QVFW2Response;
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
 pub struct QVFW2Response {
-    pub major: u64,
-    pub minor: u64,
+    pub firmware_version: FirmwareVersion,
 }
 
 impl Response for QVFW2Response {
-    fn decode(src: &mut BytesMut) -> Result<Self> {
-        if !src.starts_with(b"VERFW2:") {
-            return Err(Error::InvalidPayload(None));
-        }
-
-        let src = src.split_off(7);
-
-        let idx = src
-            .iter()
-            .copied()
-            .enumerate()
-            .find(|(_, x)| *x == b'.')
-            .map(|(i, _)| i);
-        let idx = if let Some(x) = idx {
-            x
-        } else {
-            return Err(Error::InvalidPayload(None));
-        };
-
-        let (version_major, version_minor) = src.split_at(idx);
-
-        let version_major = u64::from_str_radix(std::str::from_utf8(version_major)?, 16)?;
-        let version_minor = u64::from_str_radix(std::str::from_utf8(&version_minor[1..])?, 16)?;
-
+    fn decode(src: &[u8]) -> Result<Self> {
         Ok(Self {
-            major: version_major,
-            minor: version_minor,
+            firmware_version: FirmwareVersion::decode_prefixed(src, b"VERFW2:")?,
         })
     }
 }
@@ -57,6 +32,7 @@ mod test {
     use crate::command::{Command, Request, Response};
     use crate::commands::qvfw2::{QVFW2Response, QVFW2};
     use crate::error::Result;
+    use crate::firmware::FirmwareVersion;
     use bytes::{Buf, BytesMut};
     use crc_any::CRCu16;
     use rand::prelude::*;
@@ -79,18 +55,23 @@ mod test {
 
             let res = format!("VERFW2:{:X}.{:X}", n_maj, n_min);
 
-            let mut buf = BytesMut::from(res.as_str());
-            let item = <QVFW2 as Command>::Response::decode(&mut buf)?;
+            let buf = BytesMut::from(res.as_str());
+            let item = <QVFW2 as Command>::Response::decode(&buf)?;
 
             assert_eq!(
                 item,
                 QVFW2Response {
-                    major: n_maj,
-                    minor: n_min
+                    firmware_version: FirmwareVersion {
+                        major: n_maj,
+                        minor: n_min
+                    }
                 }
             );
         }
 
+        // The reply to `QVFW` is not taken for that to `QVFW2`.
+        assert!(<QVFW2 as Command>::Response::decode(b"VERFW:00001.02").is_err());
+
         Ok(())
     }
 
@@ -127,8 +108,10 @@ mod test {
             assert_eq!(
                 item,
                 Some(QVFW2Response {
-                    major: n_maj,
-                    minor: n_min
+                    firmware_version: FirmwareVersion {
+                        major: n_maj,
+                        minor: n_min
+                    }
                 })
             );
         }
@@ -0,0 +1,119 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::firmware::FirmwareVersion;
+use serde_derive::Serialize;
+
+// Firmware of the remote panel, where one is fitted.
+pub struct QVFW3;
+
+impl Command for QVFW3 {
+    const PROTOCOL_ID: &'static [u8] = b"QVFW3";
+    const COMMAND_NAME: &'static str = "QueryFirmwareVersion3";
+
+    type Request = ();
+    type Response = QVFW3Response;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct QVFW3Response {
+    pub firmware_version: FirmwareVersion,
+}
+
+impl Response for QVFW3Response {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Ok(Self {
+            firmware_version: FirmwareVersion::decode_prefixed(src, b"VERFW3:")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qvfw3::{QVFW3Response, QVFW3};
+    use crate::error::Result;
+    use crate::firmware::FirmwareVersion;
+    use bytes::{Buf, BytesMut};
+    use crc_any::CRCu16;
+    use rand::prelude::*;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_qvfw3_payload_encode() -> Result<()> {
+        let req: <QVFW3 as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qvfw3_payload_decode() -> Result<()> {
+        for _ in 0..1000 {
+            let n_maj: u64 = random();
+            let n_min: u64 = random();
+
+            let res = format!("VERFW3:{:X}.{:X}", n_maj, n_min);
+
+            let buf = BytesMut::from(res.as_str());
+            let item = <QVFW3 as Command>::Response::decode(&buf)?;
+
+            assert_eq!(
+                item,
+                QVFW3Response {
+                    firmware_version: FirmwareVersion {
+                        major: n_maj,
+                        minor: n_min
+                    }
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qvfw3_command_encode() -> Result<()> {
+        let mut codec = Codec::<QVFW3>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"QVFW3\xd3\xd4\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_qvfw3_command_decode() -> Result<()> {
+        let mut codec = Codec::<QVFW3>::new();
+
+        for _ in 0..1000 {
+            let n_maj: u64 = random();
+            let n_min: u64 = random();
+
+            let mut res = format!("(VERFW3:{:X}.{:X}", n_maj, n_min).into_bytes();
+            let mut crc_sum = CRCu16::crc16xmodem();
+            crc_sum.digest(res.as_slice());
+            res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+            res.push(b'\r');
+
+            let mut buf = BytesMut::from(res.as_slice());
+            let item = codec.decode(&mut buf)?;
+
+            assert_eq!(buf.remaining(), 0);
+            assert_eq!(
+                item,
+                Some(QVFW3Response {
+                    firmware_version: FirmwareVersion {
+                        major: n_maj,
+                        minor: n_min
+                    }
+                })
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use crate::command::{Ack, Command, Request};
+use crate::error::Result;
+use alloc::format;
+use alloc::vec::Vec;
+use serde_derive::Serialize;
+
+// Sets the AC output voltage.
+pub struct V;
+
+impl Command for V {
+    const PROTOCOL_ID: &'static [u8] = b"V";
+    const COMMAND_NAME: &'static str = "SetOutputVoltage";
+
+    type Request = VRequest;
+    type Response = Ack;
+}
+
+#[derive(Debug)]
+pub struct VRequest {
+    pub voltage: OutputVoltage,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum OutputVoltage {
+    V220,
+    V230,
+    V240,
+}
+
+impl OutputVoltage {
+    pub fn from_volts(volts: u16) -> Option<Self> {
+        Some(match volts {
+            220 => Self::V220,
+            230 => Self::V230,
+            240 => Self::V240,
+            _ => return None,
+        })
+    }
+
+    pub fn volts(&self) -> u16 {
+        match self {
+            Self::V220 => 220,
+            Self::V230 => 230,
+            Self::V240 => 240,
+        }
+    }
+}
+
+impl Request for VRequest {
+    fn encode(&self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(format!("{}", self.voltage.volts()).into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codec::Codec;
+    use crate::command::{Ack, Request};
+    use crate::commands::v::{OutputVoltage, VRequest, V};
+    use crate::error::Result;
+    use crate::mock::frame;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_v_payload_encode() -> Result<()> {
+        let encode = |voltage| VRequest { voltage }.encode();
+
+        assert_eq!(encode(OutputVoltage::V220)?, Some(b"220".to_vec()));
+        assert_eq!(encode(OutputVoltage::V240)?, Some(b"240".to_vec()));
+
+        assert_eq!(OutputVoltage::from_volts(230), Some(OutputVoltage::V230));
+        assert_eq!(OutputVoltage::from_volts(225), None);
+        assert_eq!(OutputVoltage::from_volts(120), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_v_command_encode() -> Result<()> {
+        let mut codec = Codec::<V>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            VRequest {
+                voltage: OutputVoltage::V230,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"V230\x9f\xf2\r");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_v_command_decode() -> Result<()> {
+        let mut codec = Codec::<V>::new();
+
+        let mut buf = BytesMut::from(frame(b"ACK").as_slice());
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use crate::error::{Error, ErrorKind, Result};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+use serde_derive::Serialize;
+
+/// Civil date and time as kept by the device clock, without a time zone.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Parse the `YYYYMMDDhhmmss` format used by the device.
+    pub(crate) fn parse(src: &str, field: &'static str) -> Result<Self> {
+        let invalid = || Error::invalid_field(ErrorKind::InvalidPayload, field, src.as_bytes());
+        if src.len() != 14 || !src.bytes().all(|x| x.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let value = DateTime {
+            year: u16::from_str(&src[0..4])?,
+            month: u8::from_str(&src[4..6])?,
+            day: u8::from_str(&src[6..8])?,
+            hour: u8::from_str(&src[8..10])?,
+            minute: u8::from_str(&src[10..12])?,
+            second: u8::from_str(&src[12..14])?,
+        };
+        if !value.is_valid() {
+            return Err(invalid());
+        }
+
+        Ok(value)
+    }
+
+    /// Whether every field is in range, including the day for the month and year.
+    pub fn is_valid(&self) -> bool {
+        let days_in_month = match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0) => 29,
+            2 => 28,
+            _ => return false,
+        };
+
+        (1..=days_in_month).contains(&self.day)
+            && self.hour <= 23
+            && self.minute <= 59
+            && self.second <= 59
+    }
+
+    /// Seconds since 1970-01-01 00:00:00 on the same clock.
+    ///
+    /// The device clock has no time zone, so this is only meaningful for comparing it with
+    /// another clock set to the same zone.
+    pub fn timestamp(&self) -> i64 {
+        // Days from civil, counting years from March so the leap day comes last.
+        let year = i64::from(self.year) - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        days * 86400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    /// Inverse of `timestamp`, e.g. to build the host time from `SystemTime` and the UTC offset of
+    /// the zone the device clock is set to.
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        let days = timestamp.div_euclid(86400) + 719_468;
+        let seconds = timestamp.rem_euclid(86400);
+
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let month = mp + if mp < 10 { 3 } else { -9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        DateTime {
+            year: year as u16,
+            month: month as u8,
+            day: (day_of_year - (153 * mp + 2) / 5 + 1) as u8,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+        }
+    }
+}
+
+/// Result of comparing the device clock with the host, see `Inverter::sync_clock`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ClockDrift {
+    pub inverter: DateTime,
+    pub host: DateTime,
+    // Seconds the device clock is ahead of the host, negative when it is behind.
+    pub drift: i64,
+    pub corrected: bool,
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::datetime::DateTime;
+    use crate::error::Result;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use std::time::Duration;
+
+    #[test]
+    fn test_datetime_timestamp() {
+        let value = DateTime {
+            year: 2020,
+            month: 10,
+            day: 18,
+            hour: 12,
+            minute: 30,
+            second: 5,
+        };
+        assert_eq!(value.timestamp(), 1_603_024_205);
+        assert_eq!(DateTime::from_timestamp(1_603_024_205), value);
+
+        assert_eq!(
+            DateTime::from_timestamp(0).to_string(),
+            "1970-01-01 00:00:00"
+        );
+        assert_eq!(
+            DateTime::from_timestamp(951_825_600).to_string(),
+            "2000-02-29 12:00:00"
+        );
+
+        for timestamp in (0..4_102_444_800).step_by(86_399 * 7) {
+            let value = DateTime::from_timestamp(timestamp);
+            assert!(value.is_valid());
+            assert_eq!(value.timestamp(), timestamp);
+        }
+    }
+
+    #[test]
+    fn test_datetime_is_valid() {
+        let value = |month, day, hour| DateTime {
+            year: 2100,
+            month,
+            day,
+            hour,
+            minute: 59,
+            second: 59,
+        };
+
+        assert!(value(12, 31, 23).is_valid());
+        assert!(!value(13, 1, 0).is_valid());
+        assert!(!value(0, 1, 0).is_valid());
+        assert!(!value(4, 31, 0).is_valid());
+        assert!(!value(1, 1, 24).is_valid());
+        assert!(!value(2, 29, 0).is_valid());
+        assert!(DateTime {
+            year: 2000,
+            ..value(2, 29, 0)
+        }
+        .is_valid());
+        assert!(DateTime::parse("20000229120000", "datetime").is_ok());
+        assert!(DateTime::parse("20010229120000", "datetime").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_clock() -> Result<()> {
+        let host = DateTime::from_timestamp(1_603_024_200);
+        let stream = MockStream::new()
+            .respond(b"QT", b"20201018122730")
+            .respond(b"DAT201018123000", b"ACK");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let drift = inverter
+            .sync_clock(|| host, Duration::from_secs(300))
+            .await?;
+        assert_eq!(drift.drift, -150);
+        assert!(!drift.corrected);
+
+        let drift = inverter
+            .sync_clock(|| host, Duration::from_secs(60))
+            .await?;
+        assert!(drift.corrected);
+
+        let requests = inverter.into_inner().requests;
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2], b"DAT201018123000");
+
+        Ok(())
+    }
+}
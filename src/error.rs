@@ -1,18 +1,49 @@
-use std::fmt::{self, Debug, Display};
-use std::num::{ParseFloatError, ParseIntError};
-use std::str::Utf8Error;
-use std::{io, result};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+use core::num::{ParseFloatError, ParseIntError};
+use core::str::Utf8Error;
+#[cfg(feature = "std")]
+use std::io;
 
-pub type Result<T> = result::Result<T, Error>;
+#[cfg(feature = "alloc")]
+pub type Result<T> = core::result::Result<T, Error>;
 
+/// Error with as much context as was available where it happened.
+///
+/// The command and frame are attached by the codecs, the field and its raw bytes by the response
+/// decoders.
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
-pub enum Error {
+pub struct Error {
+    kind: ErrorKind,
+    command: Option<&'static str>,
+    field: Option<&'static str>,
+    raw: Option<Vec<u8>>,
+    frame: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
     InvalidResponsePrefix,
     InvalidResponseCrcSum,
     InvalidResponseFormat,
 
-    InvalidPayload(Option<Box<dyn std::error::Error>>),
+    NotAcknowledged,
+    UnsupportedCommand,
 
+    // The payload is missing fields or has the wrong shape.
+    InvalidPayload,
+    TooFewFields {
+        expected: usize,
+        actual: usize,
+    },
+    TooManyFields {
+        expected: usize,
+        actual: usize,
+    },
+
+    #[cfg(feature = "std")]
     Io(io::Error),
     ParseFloat(ParseFloatError),
     ParseInt(ParseIntError),
@@ -35,37 +66,189 @@ pub enum Error {
 
     // QPIWS
     InvalidWarningStatus,
+
+    // Modbus
+    ModbusException(u8),
+}
+
+#[cfg(feature = "alloc")]
+impl Error {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            command: None,
+            field: None,
+            raw: None,
+            frame: None,
+        }
+    }
+
+    pub(crate) fn invalid_field(kind: ErrorKind, field: &'static str, raw: &[u8]) -> Self {
+        Self::new(kind).with_field(field, raw)
+    }
+
+    pub(crate) fn with_command(mut self, command: &'static str) -> Self {
+        self.command.get_or_insert(command);
+        self
+    }
+
+    pub(crate) fn with_field(mut self, field: &'static str, raw: &[u8]) -> Self {
+        self.field = Some(field);
+        self.raw = Some(raw.to_vec());
+        self
+    }
+
+    pub(crate) fn with_frame(mut self, frame: &[u8]) -> Self {
+        self.frame.get_or_insert_with(|| frame.to_vec());
+        self
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn command(&self) -> Option<&'static str> {
+        self.command
+    }
+
+    pub fn field(&self) -> Option<&'static str> {
+        self.field
+    }
+
+    pub fn raw(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
+    }
+
+    pub fn frame(&self) -> Option<&[u8]> {
+        self.frame.as_deref()
+    }
 }
 
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidResponsePrefix => write!(f, "invalid response prefix"),
+            Self::InvalidResponseCrcSum => write!(f, "invalid response CRC sum"),
+            Self::InvalidResponseFormat => write!(f, "invalid response format"),
+            Self::NotAcknowledged => write!(f, "command not acknowledged"),
+            Self::UnsupportedCommand => write!(f, "command not supported by the device"),
+            Self::InvalidPayload => write!(f, "invalid payload"),
+            Self::TooFewFields { expected, actual } => {
+                write!(f, "expected at least {} fields, got {}", expected, actual)
+            }
+            Self::TooManyFields { expected, actual } => {
+                write!(f, "expected at most {} fields, got {}", expected, actual)
+            }
+            #[cfg(feature = "std")]
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::ParseFloat(e) => write!(f, "invalid float: {}", e),
+            Self::ParseInt(e) => write!(f, "invalid integer: {}", e),
+            Self::Utf8(e) => write!(f, "invalid UTF-8: {}", e),
+            Self::InvalidDeviceStatus => write!(f, "invalid device status"),
+            Self::InvalidDeviceBatteryType => write!(f, "invalid battery type"),
+            Self::InvalidDeviceInputVoltageRange => write!(f, "invalid input voltage range"),
+            Self::InvalidDeviceOutputSourcePriority => write!(f, "invalid output source priority"),
+            Self::InvalidDeviceChargeSourcePriority => write!(f, "invalid charge source priority"),
+            Self::InvalidDeviceMachineType => write!(f, "invalid machine type"),
+            Self::InvalidDeviceTopology => write!(f, "invalid topology"),
+            Self::InvalidDeviceOutputMode => write!(f, "invalid output mode"),
+            Self::InvalidDeviceMode => write!(f, "invalid device mode"),
+            Self::InvalidWarningStatus => write!(f, "invalid warning status"),
+            Self::ModbusException(code) => write!(f, "Modbus exception {:#04x}", code),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Forward to the debug formatter.
-        Debug::fmt(self, f)
+        Display::fmt(&self.kind, f)?;
+        if let Some(command) = self.command {
+            write!(f, " in {}", command)?;
+        }
+        if let Some(field) = self.field {
+            write!(f, " at field `{}`", field)?;
+        }
+        if let Some(raw) = &self.raw {
+            write!(f, " (raw: {:?})", String::from_utf8_lossy(raw))?;
+        }
+        if let Some(frame) = &self.frame {
+            write!(f, " (frame: {:?})", String::from_utf8_lossy(frame))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(e) => Some(e),
+            ErrorKind::ParseFloat(e) => Some(e),
+            ErrorKind::ParseInt(e) => Some(e),
+            ErrorKind::Utf8(e) => Some(e),
+            _ => None,
+        }
     }
 }
 
-impl std::error::Error for Error {}
+#[cfg(feature = "alloc")]
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
 
+#[cfg(feature = "std")]
 impl From<io::Error> for Error {
     fn from(inner: io::Error) -> Self {
-        Self::Io(inner)
+        Self::new(ErrorKind::Io(inner))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<ParseFloatError> for Error {
     fn from(inner: ParseFloatError) -> Self {
-        Self::ParseFloat(inner)
+        Self::new(ErrorKind::ParseFloat(inner))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<ParseIntError> for Error {
     fn from(inner: ParseIntError) -> Self {
-        Self::ParseInt(inner)
+        Self::new(ErrorKind::ParseInt(inner))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<Utf8Error> for Error {
     fn from(inner: Utf8Error) -> Self {
-        Self::Utf8(inner)
+        Self::new(ErrorKind::Utf8(inner))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{Error, ErrorKind};
+
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    #[test]
+    fn test_error_is_send_sync() {
+        assert_send_sync::<Error>();
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = Error::invalid_field(ErrorKind::InvalidDeviceMode, "mode", b"X")
+            .with_command("DeviceModeInquiry")
+            .with_frame(b"(X\x00\x00\r");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid device mode in DeviceModeInquiry at field `mode` (raw: \"X\") (frame: \"(X\\0\\0\\r\")"
+        );
+        assert_eq!(err.raw(), Some(&b"X"[..]));
     }
 }
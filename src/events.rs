@@ -0,0 +1,156 @@
+use crate::commands::qpiws::QPIWSResponse;
+use crate::status::{pi30_warnings, Severity, Warning};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use serde_derive::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WarningRaised {
+    pub warning: Warning,
+    pub severity: Severity,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WarningCleared {
+    pub warning: Warning,
+    // Severity the warning had while it was active.
+    pub severity: Severity,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum WarningEvent {
+    Raised(WarningRaised),
+    Cleared(WarningCleared),
+}
+
+fn active_warnings(ws: &QPIWSResponse) -> BTreeMap<Warning, Severity> {
+    pi30_warnings(ws)
+        .iter()
+        .filter(|(active, _)| *active)
+        .map(|(_, warning)| (*warning, warning.severity(ws.inverter_fault)))
+        .collect()
+}
+
+/// Events that lead from one warning snapshot to the next, clearances first.
+///
+/// A warning whose severity changes between snapshots is raised again with its new severity.
+pub fn diff_warnings(previous: &QPIWSResponse, current: &QPIWSResponse) -> Vec<WarningEvent> {
+    diff(&active_warnings(previous), &active_warnings(current))
+}
+
+fn diff(
+    previous: &BTreeMap<Warning, Severity>,
+    current: &BTreeMap<Warning, Severity>,
+) -> Vec<WarningEvent> {
+    let cleared = previous
+        .iter()
+        .filter(|(warning, _)| !current.contains_key(warning))
+        .map(|(&warning, &severity)| WarningEvent::Cleared(WarningCleared { warning, severity }));
+    let raised = current
+        .iter()
+        .filter(|(warning, severity)| previous.get(warning) != Some(severity))
+        .map(|(&warning, &severity)| WarningEvent::Raised(WarningRaised { warning, severity }));
+
+    cleared.chain(raised).collect()
+}
+
+/// Turns successive QPIWS reads into warning events.
+#[derive(Debug, Default)]
+pub struct WarningTracker {
+    active: BTreeMap<Warning, Severity>,
+}
+
+impl WarningTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next snapshot. The first one raises every active warning.
+    pub fn update(&mut self, ws: &QPIWSResponse) -> Vec<WarningEvent> {
+        let current = active_warnings(ws);
+        let events = diff(&self.active, &current);
+        self.active = current;
+
+        events
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = (Warning, Severity)> + '_ {
+        self.active
+            .iter()
+            .map(|(&warning, &severity)| (warning, severity))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::Response;
+    use crate::commands::qpiws::QPIWSResponse;
+    use crate::error::Result;
+    use crate::events::{
+        diff_warnings, WarningCleared, WarningEvent, WarningRaised, WarningTracker,
+    };
+    use crate::status::{Severity, Warning};
+    use bytes::BytesMut;
+
+    fn snapshot(positions: &[usize]) -> Result<QPIWSResponse> {
+        let mut res = [b'0'; 32];
+        for &position in positions {
+            res[position] = b'1';
+        }
+
+        QPIWSResponse::decode(&BytesMut::from(&res[..]))
+    }
+
+    #[test]
+    fn test_diff_warnings() -> Result<()> {
+        // Line fail and over temperature, then over temperature escalates with the inverter
+        // fault flag and the line comes back.
+        let previous = snapshot(&[5, 9])?;
+        let current = snapshot(&[1, 9])?;
+
+        assert_eq!(
+            diff_warnings(&previous, &current),
+            vec![
+                WarningEvent::Cleared(WarningCleared {
+                    warning: Warning::LineFail,
+                    severity: Severity::Warning,
+                }),
+                WarningEvent::Raised(WarningRaised {
+                    warning: Warning::InverterFault,
+                    severity: Severity::Fault,
+                }),
+                WarningEvent::Raised(WarningRaised {
+                    warning: Warning::OverTemperature,
+                    severity: Severity::Fault,
+                }),
+            ]
+        );
+        assert_eq!(diff_warnings(&current, &current), vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_warning_tracker() -> Result<()> {
+        let mut tracker = WarningTracker::new();
+
+        assert_eq!(
+            tracker.update(&snapshot(&[2])?),
+            vec![WarningEvent::Raised(WarningRaised {
+                warning: Warning::BusOver,
+                severity: Severity::Fault,
+            })]
+        );
+        assert_eq!(tracker.update(&snapshot(&[2])?), vec![]);
+        assert_eq!(
+            tracker.update(&snapshot(&[])?),
+            vec![WarningEvent::Cleared(WarningCleared {
+                warning: Warning::BusOver,
+                severity: Severity::Fault,
+            })]
+        );
+        assert_eq!(tracker.active().count(), 0);
+
+        Ok(())
+    }
+}
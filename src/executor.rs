@@ -0,0 +1,47 @@
+use crate::command::Command;
+use crate::error::Result;
+use crate::handle::InverterHandle;
+use crate::inverter::Inverter;
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Runs PI30 commands, either on an `Inverter` directly or through an `InverterHandle` that shares
+/// the link with other tasks.
+#[async_trait]
+pub trait Executor {
+    async fn execute<C>(&mut self, req: C::Request) -> Result<C::Response>
+    where
+        C: Command + Send + 'static,
+        C::Request: Send,
+        C::Response: Send;
+}
+
+#[async_trait]
+impl<S> Executor for Inverter<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    async fn execute<C>(&mut self, req: C::Request) -> Result<C::Response>
+    where
+        C: Command + Send + 'static,
+        C::Request: Send,
+        C::Response: Send,
+    {
+        Inverter::execute::<C>(self, req).await
+    }
+}
+
+#[async_trait]
+impl<S> Executor for InverterHandle<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    async fn execute<C>(&mut self, req: C::Request) -> Result<C::Response>
+    where
+        C: Command + Send + 'static,
+        C::Request: Send,
+        C::Response: Send,
+    {
+        InverterHandle::execute::<C>(self, req).await
+    }
+}
@@ -0,0 +1,197 @@
+use crate::error::{Error, Result};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+use serde_derive::Serialize;
+
+/// Numeric fault code reported by the device while in fault mode.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum FaultCode {
+    FanLocked,
+    OverTemperature,
+    BatteryVoltageTooHigh,
+    BatteryVoltageTooLow,
+    OutputShortCircuited,
+    OutputVoltageTooHigh,
+    OverloadTimeout,
+    BusVoltageTooHigh,
+    BusSoftStartFailed,
+    PvOverCurrent,
+    PvOverVoltage,
+    DcdcOverCurrent,
+    BatteryDischargeOverCurrent,
+    OverCurrent,
+    BusVoltageTooLow,
+    InverterSoftStartFailed,
+    OverDcVoltageInAcOutput,
+    BatteryConnectionOpen,
+    CurrentSensorFailed,
+    OutputVoltageTooLow,
+    InverterNegativePower,
+    ParallelVersionDifferent,
+    OutputCircuitFailed,
+    CanCommunicationFailed,
+    ParallelHostLineLost,
+    ParallelSynchronizedSignalLost,
+    ParallelBatteryVoltageDifferent,
+    ParallelLineVoltageOrFrequencyDifferent,
+    ParallelLineInputCurrentUnbalanced,
+    ParallelOutputSettingDifferent,
+    Unknown(u8),
+}
+
+impl FaultCode {
+    /// Decode a fault code, where zero means there is no fault.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => return None,
+            1 => Self::FanLocked,
+            2 => Self::OverTemperature,
+            3 => Self::BatteryVoltageTooHigh,
+            4 => Self::BatteryVoltageTooLow,
+            5 => Self::OutputShortCircuited,
+            6 => Self::OutputVoltageTooHigh,
+            7 => Self::OverloadTimeout,
+            8 => Self::BusVoltageTooHigh,
+            9 => Self::BusSoftStartFailed,
+            10 => Self::PvOverCurrent,
+            11 => Self::PvOverVoltage,
+            12 => Self::DcdcOverCurrent,
+            13 => Self::BatteryDischargeOverCurrent,
+            51 => Self::OverCurrent,
+            52 => Self::BusVoltageTooLow,
+            53 => Self::InverterSoftStartFailed,
+            55 => Self::OverDcVoltageInAcOutput,
+            56 => Self::BatteryConnectionOpen,
+            57 => Self::CurrentSensorFailed,
+            58 => Self::OutputVoltageTooLow,
+            60 => Self::InverterNegativePower,
+            71 => Self::ParallelVersionDifferent,
+            72 => Self::OutputCircuitFailed,
+            80 => Self::CanCommunicationFailed,
+            81 => Self::ParallelHostLineLost,
+            82 => Self::ParallelSynchronizedSignalLost,
+            83 => Self::ParallelBatteryVoltageDifferent,
+            84 => Self::ParallelLineVoltageOrFrequencyDifferent,
+            85 => Self::ParallelLineInputCurrentUnbalanced,
+            86 => Self::ParallelOutputSettingDifferent,
+            x => Self::Unknown(x),
+        })
+    }
+
+    /// Parse a decimal fault code field, as sent by the device.
+    pub(crate) fn parse(src: &str, field: &'static str) -> Result<Option<Self>> {
+        match u8::from_str(src) {
+            Ok(code) => Ok(Self::from_code(code)),
+            Err(e) => Err(Error::from(e).with_field(field, src.as_bytes())),
+        }
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::FanLocked => 1,
+            Self::OverTemperature => 2,
+            Self::BatteryVoltageTooHigh => 3,
+            Self::BatteryVoltageTooLow => 4,
+            Self::OutputShortCircuited => 5,
+            Self::OutputVoltageTooHigh => 6,
+            Self::OverloadTimeout => 7,
+            Self::BusVoltageTooHigh => 8,
+            Self::BusSoftStartFailed => 9,
+            Self::PvOverCurrent => 10,
+            Self::PvOverVoltage => 11,
+            Self::DcdcOverCurrent => 12,
+            Self::BatteryDischargeOverCurrent => 13,
+            Self::OverCurrent => 51,
+            Self::BusVoltageTooLow => 52,
+            Self::InverterSoftStartFailed => 53,
+            Self::OverDcVoltageInAcOutput => 55,
+            Self::BatteryConnectionOpen => 56,
+            Self::CurrentSensorFailed => 57,
+            Self::OutputVoltageTooLow => 58,
+            Self::InverterNegativePower => 60,
+            Self::ParallelVersionDifferent => 71,
+            Self::OutputCircuitFailed => 72,
+            Self::CanCommunicationFailed => 80,
+            Self::ParallelHostLineLost => 81,
+            Self::ParallelSynchronizedSignalLost => 82,
+            Self::ParallelBatteryVoltageDifferent => 83,
+            Self::ParallelLineVoltageOrFrequencyDifferent => 84,
+            Self::ParallelLineInputCurrentUnbalanced => 85,
+            Self::ParallelOutputSettingDifferent => 86,
+            Self::Unknown(x) => *x,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::FanLocked => "Fan is locked when inverter is off",
+            Self::OverTemperature => "Over temperature",
+            Self::BatteryVoltageTooHigh => "Battery voltage is too high",
+            Self::BatteryVoltageTooLow => "Battery voltage is too low",
+            Self::OutputShortCircuited => {
+                "Output short circuited or over temperature detected by internal converter components"
+            }
+            Self::OutputVoltageTooHigh => "Output voltage is too high",
+            Self::OverloadTimeout => "Overload time out",
+            Self::BusVoltageTooHigh => "Bus voltage is too high",
+            Self::BusSoftStartFailed => "Bus soft start failed",
+            Self::PvOverCurrent => "PV over current",
+            Self::PvOverVoltage => "PV over voltage",
+            Self::DcdcOverCurrent => "DCDC over current",
+            Self::BatteryDischargeOverCurrent => "Battery discharge over current",
+            Self::OverCurrent => "Over current",
+            Self::BusVoltageTooLow => "Bus voltage is too low",
+            Self::InverterSoftStartFailed => "Inverter soft start failed",
+            Self::OverDcVoltageInAcOutput => "Over DC voltage in AC output",
+            Self::BatteryConnectionOpen => "Battery connection is open",
+            Self::CurrentSensorFailed => "Current sensor failed",
+            Self::OutputVoltageTooLow => "Output voltage is too low",
+            Self::InverterNegativePower => "Inverter negative power",
+            Self::ParallelVersionDifferent => "Parallel version different",
+            Self::OutputCircuitFailed => "Output circuit failed",
+            Self::CanCommunicationFailed => "CAN communication failed",
+            Self::ParallelHostLineLost => "Parallel host line lost",
+            Self::ParallelSynchronizedSignalLost => "Parallel synchronized signal lost",
+            Self::ParallelBatteryVoltageDifferent => "Parallel battery voltage detect different",
+            Self::ParallelLineVoltageOrFrequencyDifferent => {
+                "Parallel line voltage or frequency detect different"
+            }
+            Self::ParallelLineInputCurrentUnbalanced => "Parallel line input current unbalanced",
+            Self::ParallelOutputSettingDifferent => "Parallel output setting different",
+            Self::Unknown(_) => "Unknown fault",
+        }
+    }
+}
+
+impl Display for FaultCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}: {}", self.code(), self.description())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fault::FaultCode;
+
+    #[test]
+    fn test_fault_code_roundtrip() {
+        assert_eq!(FaultCode::from_code(0), None);
+        for code in 1..=255 {
+            assert_eq!(FaultCode::from_code(code).unwrap().code(), code);
+        }
+
+        assert_eq!(
+            FaultCode::from_code(58),
+            Some(FaultCode::OutputVoltageTooLow)
+        );
+        assert_eq!(FaultCode::from_code(14), Some(FaultCode::Unknown(14)));
+    }
+
+    #[test]
+    fn test_fault_code_display() {
+        assert_eq!(
+            FaultCode::FanLocked.to_string(),
+            "01: Fan is locked when inverter is off"
+        );
+    }
+}
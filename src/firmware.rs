@@ -0,0 +1,148 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use core::fmt::{self, Display};
+use core::str::FromStr;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+
+/// Firmware version as reported by `QVFW`, `QVFW2` and `QVFW3`: two hexadecimal numbers, shown
+/// by the device as `xxxxx.yy`.
+///
+/// Versions order by major, then minor number. They are serialized in the device form.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FirmwareVersion {
+    pub major: u64,
+    pub minor: u64,
+}
+
+impl FirmwareVersion {
+    pub fn new(major: u64, minor: u64) -> Self {
+        Self { major, minor }
+    }
+
+    fn parse(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b'.', 2..=2)?;
+
+        Ok(Self {
+            major: u64::from_str_radix(fields.str("major")?, 16)?,
+            minor: u64::from_str_radix(fields.str("minor")?, 16)?,
+        })
+    }
+
+    // The queries prefix the version with `VERFW:`, `VERFW2:` and `VERFW3:` respectively, so a
+    // reply to another one is rejected.
+    pub(crate) fn decode_prefixed(src: &[u8], prefix: &[u8]) -> Result<Self> {
+        match src.strip_prefix(prefix) {
+            Some(version) => Self::parse(version),
+            None => Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "firmware_version",
+                src,
+            )),
+        }
+    }
+}
+
+/// Parse the `xxxxx.yy` format, e.g. to compare against a known version.
+impl FromStr for FirmwareVersion {
+    type Err = Error;
+
+    fn from_str(src: &str) -> Result<Self> {
+        Self::parse(src.as_bytes())
+    }
+}
+
+impl Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:05X}.{:02X}", self.major, self.minor)
+    }
+}
+
+impl Serialize for FirmwareVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for FirmwareVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        struct VersionVisitor;
+
+        impl Visitor<'_> for VersionVisitor {
+            type Value = FirmwareVersion;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a firmware version in the form xxxxx.yy")
+            }
+
+            fn visit_str<E: de::Error>(self, src: &str) -> core::result::Result<Self::Value, E> {
+                src.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(src), &self))
+            }
+        }
+
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Result;
+    use crate::firmware::FirmwareVersion;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn test_firmware_version_decode() -> Result<()> {
+        assert_eq!(
+            FirmwareVersion::decode_prefixed(b"VERFW:00072.70", b"VERFW:")?,
+            FirmwareVersion::new(0x72, 0x70)
+        );
+        assert_eq!(
+            FirmwareVersion::decode_prefixed(b"VERFW3:00001.0A", b"VERFW3:")?,
+            FirmwareVersion::new(0x1, 0xa)
+        );
+
+        let decode = |src: &[u8]| FirmwareVersion::decode_prefixed(src, b"VERFW:");
+        assert!(decode(b"00072.70").is_err());
+        assert!(decode(b"VERSION:00072.70").is_err());
+        assert!(decode(b"VERFW2:00072.70").is_err());
+        assert!(decode(b"VERFW:00072").is_err());
+        assert!(decode(b"VERFW:00072.70.1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_firmware_version_display() -> Result<()> {
+        let version = FirmwareVersion::new(0x72, 0x70);
+        assert_eq!(version.to_string(), "00072.70");
+        assert_eq!(version.to_string().parse::<FirmwareVersion>()?, version);
+
+        assert_eq!(FirmwareVersion::new(0x1, 0xa).to_string(), "00001.0A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_firmware_version_ordering() -> Result<()> {
+        let version = FirmwareVersion::new(0x72, 0x70);
+
+        assert!(version > "00072.6F".parse()?);
+        assert!(version < "00072.71".parse()?);
+        assert!(version < "00100.00".parse()?);
+        assert!(version > "0000A.FF".parse()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_firmware_version_serde() {
+        assert_tokens(&FirmwareVersion::new(0x72, 0x70), &[Token::Str("00072.70")]);
+        assert_tokens(&FirmwareVersion::new(0x1, 0xa), &[Token::Str("00001.0A")]);
+
+        assert_de_tokens_error::<FirmwareVersion>(
+            &[Token::Str("00072")],
+            "invalid value: string \"00072\", expected a firmware version in the form xxxxx.yy",
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Frame extraction and CRC sums for the PI30 and PI17 protocols, on plain byte slices.
+//!
+//! Nothing here allocates, so it can be used to pick frames out of a receive buffer on targets
+//! without an allocator. With the `alloc` feature the payloads can be decoded into responses.
+
+#[cfg(feature = "alloc")]
+use crate::command::{Command, Request, Response};
+use crate::error::ErrorKind;
+#[cfg(feature = "alloc")]
+use crate::error::{Error, Result};
+#[cfg(feature = "alloc")]
+use alloc::{format, vec::Vec};
+use core::result;
+use core::str::from_utf8;
+use crc_any::CRCu16;
+use log::{debug, trace};
+
+/// A frame found at the start of a receive buffer.
+#[derive(Debug)]
+pub struct Frame<'a> {
+    /// Bytes to drop from the buffer, including anything skipped before the frame.
+    pub consumed: usize,
+    /// The frame as received.
+    pub raw: &'a [u8],
+    /// The payload, or why the frame was rejected.
+    pub payload: result::Result<&'a [u8], ErrorKind>,
+}
+
+impl<'a> Frame<'a> {
+    fn rejected(consumed: usize, raw: &'a [u8], kind: ErrorKind) -> Self {
+        Self {
+            consumed,
+            raw,
+            payload: Err(kind),
+        }
+    }
+
+    /// Decode the payload as the response to `C`.
+    #[cfg(feature = "alloc")]
+    pub fn decode<C: Command>(self) -> Result<C::Response> {
+        self.decode_with::<C>(C::Response::decode)
+    }
+
+    /// Decode the payload as the response to `C`, see `Response::decode_lenient`.
+    #[cfg(feature = "alloc")]
+    pub fn decode_lenient<C: Command>(self) -> Result<C::Response> {
+        self.decode_with::<C>(C::Response::decode_lenient)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode_with<C: Command>(
+        self,
+        decode: fn(&[u8]) -> Result<C::Response>,
+    ) -> Result<C::Response> {
+        let raw = self.raw;
+        match self.payload {
+            Ok(payload) => decode(payload),
+            Err(kind) => Err(Error::new(kind)),
+        }
+        .map_err(|e| e.with_command(C::COMMAND_NAME).with_frame(raw))
+    }
+}
+
+pub fn crc(data: &[u8]) -> u16 {
+    let mut crc_sum = CRCu16::crc16xmodem();
+    crc_sum.digest(data);
+
+    crc_sum.get_crc()
+}
+
+/// Find the first PI30 frame (`(payload CRC \r`) in `src`, if it is complete.
+pub fn decode_pi30(src: &[u8]) -> Option<Frame<'_>> {
+    let index = src.iter().position(|&x| x == b'\r')?;
+
+    let mut item = &src[..index];
+    let mut recover_length = 0;
+    if item.first() != Some(&b'(') {
+        trace!("Invalid response format: {:?}", item);
+
+        // Try to recover by removing everything until the first (
+        match item.iter().position(|&x| x == b'(') {
+            Some(start) => {
+                trace!("Attempting to recover from invalid response");
+                recover_length = start;
+                item = &item[start..];
+            }
+            None => {
+                return Some(Frame::rejected(
+                    src.len(),
+                    item,
+                    ErrorKind::InvalidResponseFormat,
+                ))
+            }
+        }
+    }
+
+    // The CRC sum may itself contain a carriage return, in which case the frame ends at the next
+    // one.
+    for step in 0..3 {
+        // Check the CRC for the current detected payload.
+        if item.len() >= 3 {
+            let (data, crc_sum) = item.split_at(item.len() - 2);
+            if crc(data) == u16::from_be_bytes([crc_sum[0], crc_sum[1]]) {
+                break;
+            }
+        }
+
+        // Check src length.
+        // Ensure the new command response ends in \r.
+        if index + step + 1 == src.len()
+            || !match step {
+                0 => {
+                    let check = src[index + 1] == b'\r';
+                    if !check && (index + step + 2 < src.len() && src[index + 2] == b'\r') {
+                        continue;
+                    }
+
+                    check
+                }
+                1 => src[index + 2] == b'\r',
+                _ => false,
+            }
+        {
+            return Some(Frame::rejected(
+                src.len(),
+                item,
+                ErrorKind::InvalidResponseCrcSum,
+            ));
+        }
+
+        item = &src[recover_length..index + step + 1];
+    }
+
+    let consumed = recover_length + item.len() + 1;
+    let payload = &item[1..item.len() - 2];
+    Some(Frame {
+        consumed,
+        raw: &src[recover_length..consumed],
+        payload: if payload == b"NAK" {
+            Err(ErrorKind::NotAcknowledged)
+        } else {
+            Ok(payload)
+        },
+    })
+}
+
+/// Find the first PI17 frame (`^D`, `^0` or `^1`) in `src`, if it is complete.
+///
+/// PI18 uses the same framing.
+pub fn decode_pi17(src: &[u8]) -> Option<Frame<'_>> {
+    // Skip everything until the start of a frame.
+    let start = match src.iter().position(|&x| x == b'^') {
+        Some(start) => start,
+        None if src.contains(&b'\r') => {
+            return Some(Frame::rejected(
+                src.len(),
+                src,
+                ErrorKind::InvalidResponseFormat,
+            ))
+        }
+        None => return None,
+    };
+    if start > 0 {
+        trace!("Invalid response format: {:?}", &src[..start]);
+    }
+
+    let item = &src[start..];
+    if item.len() < 2 {
+        return None;
+    }
+
+    let frame_len = match item[1] {
+        b'0' | b'1' => 5,
+        b'D' => {
+            if item.len() < 5 {
+                return None;
+            }
+
+            match from_utf8(&item[2..5])
+                .ok()
+                .and_then(|x| x.parse::<usize>().ok())
+            {
+                Some(len) if len >= 3 => 5 + len,
+                _ => {
+                    return Some(Frame::rejected(
+                        src.len(),
+                        item,
+                        ErrorKind::InvalidResponseFormat,
+                    ))
+                }
+            }
+        }
+        _ => {
+            return Some(Frame::rejected(
+                src.len(),
+                item,
+                ErrorKind::InvalidResponsePrefix,
+            ))
+        }
+    };
+
+    if item.len() < frame_len {
+        return None;
+    }
+
+    let raw = &item[..frame_len];
+    let consumed = start + frame_len;
+    if raw[frame_len - 1] != b'\r' {
+        return Some(Frame::rejected(
+            consumed,
+            raw,
+            ErrorKind::InvalidResponseFormat,
+        ));
+    }
+
+    let (data, crc_sum) = raw[..frame_len - 1].split_at(frame_len - 3);
+    if crc(data) != u16::from_be_bytes([crc_sum[0], crc_sum[1]]) {
+        return Some(Frame::rejected(
+            consumed,
+            raw,
+            ErrorKind::InvalidResponseCrcSum,
+        ));
+    }
+
+    Some(Frame {
+        consumed,
+        raw,
+        payload: match raw[1] {
+            b'0' => {
+                debug!("Response not acknowledged.");
+                Err(ErrorKind::NotAcknowledged)
+            }
+            // Answered like a PI30 `ACK`, so that setter replies decode the same on both.
+            b'1' => Ok(b"ACK"),
+            _ => Ok(&raw[5..frame_len - 3]),
+        },
+    })
+}
+
+/// Encode a PI30 request: command id, payload (if any), CRC sum and carriage return.
+#[cfg(feature = "alloc")]
+pub fn encode_pi30<C: Command>(req: &C::Request) -> Result<Vec<u8>> {
+    let mut dst = C::PROTOCOL_ID.to_vec();
+    if let Some(request_payload) = req.encode()? {
+        dst.extend_from_slice(&request_payload);
+    }
+
+    let crc_sum = crc(&dst);
+    dst.extend_from_slice(&crc_sum.to_be_bytes());
+    dst.push(b'\r');
+
+    Ok(dst)
+}
+
+/// Encode a PI17 request.
+///
+/// Requests are framed as `^P` (queries) or `^S` (setters), followed by the number of bytes left
+/// in the frame (command, payload, CRC sum and carriage return) as three decimal digits.
+#[cfg(feature = "alloc")]
+pub fn encode_pi17<C: Command>(req: &C::Request) -> Result<Vec<u8>> {
+    // Requests carrying a payload are setters.
+    let request_payload = req.encode()?;
+    let frame_type = match request_payload {
+        Some(_) => b'S',
+        None => b'P',
+    };
+
+    let len = C::PROTOCOL_ID.len() + request_payload.as_ref().map_or(0, |x| x.len()) + 3;
+    let mut dst = Vec::with_capacity(len + 5);
+    dst.push(b'^');
+    dst.push(frame_type);
+    dst.extend_from_slice(format!("{:03}", len).as_bytes());
+    dst.extend_from_slice(C::PROTOCOL_ID);
+    if let Some(request_payload) = request_payload {
+        dst.extend_from_slice(&request_payload);
+    }
+
+    let crc_sum = crc(&dst);
+    dst.extend_from_slice(&crc_sum.to_be_bytes());
+    dst.push(b'\r');
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::commands::qmod::{DeviceMode, QMOD};
+    use crate::error::{ErrorKind, Result};
+    use crate::frame::{decode_pi17, decode_pi30};
+    use crate::mock::{frame, frame_pi17};
+
+    #[test]
+    fn test_decode_pi30() -> Result<()> {
+        let mut src = b"xx".to_vec();
+        src.extend(frame(b"B"));
+        src.extend(frame(b"NAK"));
+
+        let item = decode_pi30(&src).unwrap();
+        assert_eq!(item.consumed, src.len() - frame(b"NAK").len());
+        assert_eq!(item.raw, frame(b"B").as_slice());
+        assert_eq!(item.decode::<QMOD>()?.mode, DeviceMode::BatteryMode);
+
+        let nak = frame(b"NAK");
+        let item = decode_pi30(&nak).unwrap();
+        assert!(matches!(item.payload, Err(ErrorKind::NotAcknowledged)));
+
+        assert!(decode_pi30(b"(B").is_none());
+
+        // Frames too short to hold a CRC sum are rejected rather than panicking.
+        for src in &[&b"\r"[..], b"(\r", b"(a\r", b"(ab\r\r\r\r", b"\r\r\r"] {
+            assert!(decode_pi30(src).unwrap().payload.is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_pi17() {
+        let mut src = frame_pi17(b'1', b"");
+        src.extend(frame_pi17(b'D', b"17"));
+
+        let item = decode_pi17(&src).unwrap();
+        assert_eq!(item.consumed, 5);
+        assert_eq!(item.payload.unwrap(), b"ACK");
+
+        let item = decode_pi17(&src[5..]).unwrap();
+        assert_eq!(item.payload.unwrap(), b"17");
+
+        assert!(decode_pi17(&src[5..10]).is_none());
+        assert!(matches!(
+            decode_pi17(b"^X").unwrap().payload,
+            Err(ErrorKind::InvalidResponsePrefix)
+        ));
+    }
+}
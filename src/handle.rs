@@ -0,0 +1,220 @@
+use crate::backend::Backend;
+use crate::command::Command;
+use crate::error::{Error, Result};
+use crate::inverter::Inverter;
+use crate::settings::InverterSettings;
+use crate::status::InverterStatus;
+use async_trait::async_trait;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{mpsc, oneshot};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+type Job<S> = Box<dyn for<'a> FnOnce(&'a mut Inverter<S>) -> BoxFuture<'a, ()> + Send>;
+
+/// Cloneable handle to an `Inverter` owned by a background task.
+///
+/// Requests from every clone are queued and run one at a time, in the order they were made. The
+/// pause between commands is set on the inverter with `Inverter::with_command_gap`. A request
+/// whose caller goes away is still run to the end, so the link stays in sync.
+///
+/// The task stops once every handle has been dropped.
+pub struct InverterHandle<S> {
+    jobs: mpsc::UnboundedSender<Job<S>>,
+}
+
+impl<S> Clone for InverterHandle<S> {
+    fn clone(&self) -> Self {
+        Self {
+            jobs: self.jobs.clone(),
+        }
+    }
+}
+
+impl<S> InverterHandle<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    /// Move `inverter` into a new task on the current runtime.
+    pub fn spawn(mut inverter: Inverter<S>) -> Self {
+        let (jobs, mut queue) = mpsc::unbounded_channel::<Job<S>>();
+        tokio::spawn(async move {
+            while let Some(job) = queue.recv().await {
+                job(&mut inverter).await;
+            }
+        });
+
+        Self { jobs }
+    }
+
+    /// Run `f` with exclusive access to the inverter, e.g. to send several commands back to back.
+    pub async fn run<F, T>(&self, f: F) -> Result<T>
+    where
+        F: for<'a> FnOnce(&'a mut Inverter<S>) -> BoxFuture<'a, Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let job: Job<S> = Box::new(move |inverter| {
+            Box::pin(async move {
+                // The caller may have stopped waiting.
+                let _ = tx.send(f(inverter).await);
+            })
+        });
+
+        self.jobs.send(job).map_err(|_| stopped())?;
+        rx.await.map_err(|_| stopped())?
+    }
+
+    /// Keep codes unknown to the decoders as `Unknown` enum variants for the requests queued from
+    /// now on, see `Response::decode_lenient`. The initial setting is taken from the inverter.
+    pub async fn set_lenient_decoding(&self, lenient_decoding: bool) -> Result<()> {
+        self.run(move |inverter| {
+            Box::pin(async move {
+                inverter.set_lenient_decoding(lenient_decoding);
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    pub async fn execute<C>(&self, req: C::Request) -> Result<C::Response>
+    where
+        C: Command + Send + 'static,
+        C::Request: Send,
+        C::Response: Send,
+    {
+        self.run(move |inverter| Box::pin(inverter.execute::<C>(req)))
+            .await
+    }
+
+    pub async fn execute_pi17<C>(&self, req: C::Request) -> Result<C::Response>
+    where
+        C: Command + Send + 'static,
+        C::Request: Send,
+        C::Response: Send,
+    {
+        self.run(move |inverter| Box::pin(inverter.execute_pi17::<C>(req)))
+            .await
+    }
+
+    // PI18 shares the PI17 framing.
+    pub async fn execute_pi18<C>(&self, req: C::Request) -> Result<C::Response>
+    where
+        C: Command + Send + 'static,
+        C::Request: Send,
+        C::Response: Send,
+    {
+        self.execute_pi17::<C>(req).await
+    }
+}
+
+// Each snapshot is taken in one go, so it isn't interleaved with other callers' commands.
+#[async_trait]
+impl<S> Backend for InverterHandle<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    async fn status(&mut self) -> Result<InverterStatus> {
+        self.run(|inverter| inverter.status()).await
+    }
+
+    async fn settings(&mut self) -> Result<InverterSettings> {
+        self.run(|inverter| inverter.settings()).await
+    }
+}
+
+fn stopped() -> Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "inverter task stopped").into()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::Backend;
+    use crate::commands::qmod::{DeviceMode, QMOD};
+    use crate::commands::qpi::QPI;
+    use crate::commands::qpiri::QPIRI;
+    use crate::error::{ErrorKind, Result};
+    use crate::handle::InverterHandle;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use std::time::{Duration, Instant};
+
+    fn stream() -> MockStream {
+        MockStream::new()
+            .respond(b"QMOD", b"B")
+            .respond(
+                b"QPIGS",
+                b"001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0000 074.9 27.12 00005 10110110 17 04 00010 100",
+            )
+            .respond(b"QPIWS", b"00000100000000000000000000000000")
+            .respond(
+                b"QPIRI",
+                b"230.0 13.0 230.0 50.0 13.0 3000 2400 24.0 23.0 21.0 28.2 27.0 0 30 60 0 0 0 - 01 1 0 27.0 0 0",
+            )
+    }
+
+    #[tokio::test]
+    async fn test_handle_concurrent_callers() -> Result<()> {
+        let handle = InverterHandle::spawn(Inverter::from_stream(stream()));
+        let mut poller = handle.clone();
+        let ui = handle.clone();
+
+        let (mode, status, settings) = tokio::join!(
+            ui.execute::<QMOD>(()),
+            poller.status(),
+            handle.execute::<QPIRI>(())
+        );
+        assert_eq!(mode?.mode, DeviceMode::BatteryMode);
+        assert_eq!(status?.mode, DeviceMode::BatteryMode);
+        assert_eq!(settings?.battery_bulk_voltage, 28.2);
+
+        // Unknown commands are NAKed without stopping the task.
+        assert!(handle.execute::<QPI>(()).await.is_err());
+        assert_eq!(
+            handle.execute::<QMOD>(()).await?.mode,
+            DeviceMode::BatteryMode
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handle_lenient_decoding() -> Result<()> {
+        let handle = InverterHandle::spawn(Inverter::from_stream(
+            MockStream::new().respond(b"QMOD", b"X"),
+        ));
+
+        let err = handle.execute::<QMOD>(()).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidDeviceMode));
+
+        handle.set_lenient_decoding(true).await?;
+        assert_eq!(
+            handle.execute::<QMOD>(()).await?.mode,
+            DeviceMode::Unknown(String::from("X"))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handle_command_gap() -> Result<()> {
+        let gap = Duration::from_millis(20);
+        let handle = InverterHandle::spawn(Inverter::from_stream(stream()).with_command_gap(gap));
+
+        let start = Instant::now();
+        let (a, b, c) = tokio::join!(
+            handle.execute::<QMOD>(()),
+            handle.execute::<QMOD>(()),
+            handle.execute::<QMOD>(())
+        );
+        a?;
+        b?;
+        c?;
+        assert!(start.elapsed() >= gap * 2);
+
+        Ok(())
+    }
+}
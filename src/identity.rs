@@ -0,0 +1,86 @@
+use alloc::string::String;
+use serde_derive::Serialize;
+
+/// What a unit reports about itself, built by `Inverter::identity`.
+///
+/// Parts the device doesn't answer for are left out. The serial number is the one to key units
+/// by: it is the long `QSID` serial where available and the `QID` one otherwise, as sent.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DeviceIdentity {
+    pub protocol_id: u64,
+    pub serial_number: Option<String>,
+    pub model_name: Option<String>,
+    pub general_model_number: Option<String>,
+}
+
+impl DeviceIdentity {
+    pub fn new(protocol_id: u64) -> Self {
+        Self {
+            protocol_id,
+            serial_number: None,
+            model_name: None,
+            general_model_number: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Result;
+    use crate::identity::DeviceIdentity;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+
+    #[tokio::test]
+    async fn test_identity_pi30() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QPI", b"PI30")
+            .respond(b"QSID", b"1492932105105335005535")
+            .respond(b"QID", b"92932105105335")
+            .respond(b"QMN", b"MKS2-5000")
+            .respond(b"QGMN", b"044");
+        let mut inverter = Inverter::from_stream(stream);
+
+        assert_eq!(
+            inverter.identity().await?,
+            DeviceIdentity {
+                protocol_id: 30,
+                serial_number: Some(String::from("92932105105335")),
+                model_name: Some(String::from("MKS2-5000")),
+                general_model_number: Some(String::from("044")),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_identity_pi30_without_qsid() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QPI", b"PI30")
+            .respond(b"QID", b"0092932105105335");
+        let mut inverter = Inverter::from_stream(stream);
+        inverter.negotiate().await?;
+
+        let identity = inverter.identity().await?;
+        assert_eq!(identity.serial_number.as_deref(), Some("0092932105105335"));
+        assert_eq!(identity.model_name, None);
+        assert_eq!(identity.general_model_number, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_identity_pi18() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"^P005PI", b"18")
+            .respond(b"^P005ID", b"1496161801100025000000");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let identity = inverter.identity().await?;
+        assert_eq!(identity.protocol_id, 18);
+        assert_eq!(identity.serial_number.as_deref(), Some("96161801100025"));
+
+        Ok(())
+    }
+}
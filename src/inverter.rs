@@ -1,26 +1,99 @@
+use crate::capabilities::{Capabilities, Pi30Probe};
 use crate::codec::Codec;
 use crate::command::Command;
-use crate::error::{Error, Result};
+use crate::commands::dat::{DATRequest, DAT};
+use crate::commands::qfr::{FaultRecord, QFRRequest, QFR};
+use crate::commands::qgmn::QGMN;
+use crate::commands::qid::QID;
+use crate::commands::qmn::QMN;
+use crate::commands::qpi::{QPIResponse, QPI};
+use crate::commands::qpigs::QPIGS;
+use crate::commands::qpigs2::QPIGS2;
+use crate::commands::qsid::QSID;
+use crate::commands::qt::QT;
+use crate::commands::qvfw::QVFW;
+use crate::commands::qvfw2::QVFW2;
+use crate::commands::qvfw3::QVFW3;
+use crate::datetime::{ClockDrift, DateTime};
+use crate::error::{Error, ErrorKind, Result};
+use crate::identity::DeviceIdentity;
+use crate::pi17;
+use crate::pi17::commands::gs::GS;
+use crate::pi17::commands::pi::PI;
+use crate::pi18::commands::gs::GS as PI18GS;
+use crate::pi18::commands::id::ID;
+use crate::pv::PvStatus;
 use bytes::{Buf, BytesMut};
-use log::trace;
-use tokio::io::{AsyncRead, AsyncWrite, ErrorKind};
+use log::{debug, trace};
+use std::time::Duration;
+use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio::prelude::*;
+use tokio::time::{delay_until, timeout, Instant};
 use tokio_util::codec::{Decoder, Encoder};
 
 // TODO: Find a better way to temporarily move out of the struct (within the same method).
 pub struct Inverter<S> {
     stream: S,
     buffer_in: BytesMut,
+    capabilities: Option<Capabilities>,
+    timeout: Option<Duration>,
+    command_gap: Duration,
+    last_command: Option<Instant>,
+    timed_out: bool,
+    lenient_decoding: bool,
 }
 
+// How long the stream has to stay silent after a timeout before the late reply counts as over.
+const DRAIN_QUIET: Duration = Duration::from_millis(100);
+
 impl<S> Inverter<S> {
     pub fn from_stream(stream: S) -> Self {
         Self {
             stream,
             buffer_in: BytesMut::new(),
+            capabilities: None,
+            timeout: None,
+            command_gap: Duration::from_secs(0),
+            last_command: None,
+            timed_out: false,
+            lenient_decoding: false,
         }
     }
 
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Give up on a command whose response hasn't arrived within `timeout`. Whatever arrives of it
+    /// later is drained before the next command is sent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Wait at least `gap` after each response before sending the next command. Some firmware
+    /// drops commands that follow the previous one too closely.
+    pub fn with_command_gap(mut self, gap: Duration) -> Self {
+        self.command_gap = gap;
+        self
+    }
+
+    /// Keep codes unknown to the decoders as `Unknown` enum variants, see
+    /// `Response::decode_lenient`.
+    pub fn with_lenient_decoding(mut self, lenient_decoding: bool) -> Self {
+        self.set_lenient_decoding(lenient_decoding);
+        self
+    }
+
+    pub(crate) fn set_lenient_decoding(&mut self, lenient_decoding: bool) {
+        self.lenient_decoding = lenient_decoding;
+    }
+
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
     pub fn into_inner(self) -> S {
         // WARNING: From this point onwards, `buffer_in` is lost! Reading may not be synchronized.
         self.stream
@@ -31,13 +104,206 @@ impl<S> Inverter<S>
 where
     S: AsyncRead + AsyncWrite + Unpin,
 {
-    #[allow(unused)]
+    /// Query the protocol id, firmware versions, model name and second PV input and restrict
+    /// further commands to those the device supports.
+    ///
+    /// Some PI17 and PI18 units don't answer `QPI` at all, so detecting them needs a timeout.
+    pub async fn negotiate(&mut self) -> Result<&Capabilities> {
+        self.capabilities = None;
+
+        let protocol_id = self.protocol_id().await?;
+        let probe = if Pi30Probe::applies_to(protocol_id) {
+            Some(Pi30Probe {
+                firmware_version: optional::<QVFW>(self.execute::<QVFW>(()).await)?
+                    .map(|x| x.firmware_version),
+                firmware_version2: optional::<QVFW2>(self.execute::<QVFW2>(()).await)?
+                    .map(|x| x.firmware_version),
+                firmware_version3: optional::<QVFW3>(self.execute::<QVFW3>(()).await)?
+                    .map(|x| x.firmware_version),
+                model_name: optional::<QMN>(self.execute::<QMN>(()).await)?.map(|x| x.model_name),
+                dual_mppt: optional::<QPIGS2>(self.execute::<QPIGS2>(()).await)?.is_some(),
+            })
+        } else {
+            None
+        };
+
+        let capabilities = Capabilities::negotiated(protocol_id, probe);
+        Ok(self.capabilities.get_or_insert(capabilities))
+    }
+
+    /// Query the serial number, model name and general model number, using the negotiated
+    /// protocol if there is one.
+    pub async fn identity(&mut self) -> Result<DeviceIdentity> {
+        let protocol_id = match self.capabilities() {
+            Some(capabilities) => capabilities.protocol_id,
+            None => self.protocol_id().await?,
+        };
+
+        let mut identity = DeviceIdentity::new(protocol_id);
+        match protocol_id {
+            // PI17 has no serial number query.
+            17 => {}
+            18 => {
+                identity.serial_number =
+                    optional::<ID>(self.execute_pi18::<ID>(()).await)?.map(|x| x.serial_number);
+            }
+            _ => {
+                // `QID` may truncate long serial numbers, so it is only asked when `QSID` fails.
+                identity.serial_number = match optional::<QSID>(self.execute::<QSID>(()).await)? {
+                    Some(x) => Some(x.serial_number),
+                    None => {
+                        optional::<QID>(self.execute::<QID>(()).await)?.map(|x| x.serial_number)
+                    }
+                };
+                identity.model_name =
+                    optional::<QMN>(self.execute::<QMN>(()).await)?.map(|x| x.model_name);
+                identity.general_model_number = optional::<QGMN>(self.execute::<QGMN>(()).await)?
+                    .map(|x| x.general_model_number);
+            }
+        }
+
+        Ok(identity)
+    }
+
+    /// Query the PV inputs, including the second one of dual-MPPT PI30 units, using the
+    /// negotiated protocol if there is one.
+    pub async fn pv_status(&mut self) -> Result<PvStatus> {
+        Ok(match self.capabilities().map(|x| x.protocol_id) {
+            Some(17) => PvStatus::from(&self.execute_pi17::<GS>(()).await?),
+            Some(18) => PvStatus::from(&self.execute_pi18::<PI18GS>(()).await?),
+            _ => {
+                let gs = self.execute::<QPIGS>(()).await?;
+                let gs2 = optional::<QPIGS2>(self.execute::<QPIGS2>(()).await)?;
+                PvStatus::from((&gs, gs2.as_ref()))
+            }
+        })
+    }
+
+    // PI17 and PI18 devices don't understand `QPI`, so fall back to their own protocol query.
+    async fn protocol_id(&mut self) -> Result<u64> {
+        Ok(match pi30_protocol_id(self.execute::<QPI>(()).await)? {
+            Some(x) => x,
+            None => self.execute_pi17::<PI>(()).await?.protocol_id,
+        })
+    }
+
     pub async fn execute<C: Command>(&mut self, req: C::Request) -> Result<C::Response> {
+        check_supported::<C>(self.capabilities.as_ref())?;
+        let codec = Codec::<C>::new().with_lenient_decoding(self.lenient_decoding);
+        self.transact(codec, req).await
+    }
+
+    pub async fn execute_pi17<C: Command>(&mut self, req: C::Request) -> Result<C::Response> {
+        check_supported::<C>(self.capabilities.as_ref())?;
+        let codec = pi17::codec::Codec::<C>::new().with_lenient_decoding(self.lenient_decoding);
+        self.transact(codec, req).await
+    }
+
+    // PI18 shares the PI17 framing.
+    pub async fn execute_pi18<C: Command>(&mut self, req: C::Request) -> Result<C::Response> {
+        self.execute_pi17::<C>(req).await
+    }
+
+    /// Retrieve up to `count` of the most recent faults, newest first. The device only keeps the
+    /// last 100, so larger counts read all of them.
+    pub async fn fault_history(&mut self, count: u8) -> Result<Vec<FaultRecord>> {
+        let mut records = Vec::new();
+        for index in 0..count.min(QFRRequest::MAX_INDEX + 1) {
+            match self.execute::<QFR>(QFRRequest { index }).await?.record {
+                Some(record) => records.push(record),
+                None => break,
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Compare the device clock with the host clock and set it when they are more than
+    /// `max_drift` apart.
+    ///
+    /// `now` gives the host time in the zone the device clock is set to. It is read right after
+    /// the device answers, and again for the new setting.
+    pub async fn sync_clock<F>(&mut self, mut now: F, max_drift: Duration) -> Result<ClockDrift>
+    where
+        F: FnMut() -> DateTime,
+    {
+        let inverter = self.execute::<QT>(()).await?.datetime;
+        let host = now();
+
+        let drift = inverter.timestamp() - host.timestamp();
+        let corrected = drift.unsigned_abs() > max_drift.as_secs();
+        if corrected {
+            debug!("Correcting clock drift of {} s", drift);
+            self.execute::<DAT>(DATRequest { datetime: now() }).await?;
+        }
+
+        Ok(ClockDrift {
+            inverter,
+            host,
+            drift,
+            corrected,
+        })
+    }
+
+    pub(crate) async fn transact<D, I>(&mut self, codec: D, req: I) -> Result<D::Item>
+    where
+        D: Decoder<Error = Error> + Encoder<I, Error = Error>,
+    {
+        if let Some(last_command) = self.last_command {
+            delay_until(last_command + self.command_gap).await;
+        }
+
+        if self.timed_out {
+            self.drain().await?;
+            self.timed_out = false;
+        }
+
+        let res = match self.timeout {
+            Some(duration) => match timeout(duration, self.exchange(codec, req)).await {
+                Ok(res) => res,
+                Err(_) => {
+                    // Whatever arrived so far belongs to the abandoned response.
+                    self.buffer_in.clear();
+                    self.timed_out = true;
+                    Err(io::Error::from(io::ErrorKind::TimedOut).into())
+                }
+            },
+            None => self.exchange(codec, req).await,
+        };
+        self.last_command = Some(Instant::now());
+
+        res
+    }
+
+    // Discard the late reply to a timed out command until the stream goes quiet, so that it isn't
+    // taken for the answer to the next one.
+    async fn drain(&mut self) -> Result<()> {
+        let deadline = self.timeout.map(|x| Instant::now() + x);
+        let mut chunk = [0u8; 1024];
+        loop {
+            let quiet = match deadline {
+                Some(deadline) if Instant::now() >= deadline => break,
+                Some(deadline) => DRAIN_QUIET.min(deadline - Instant::now()),
+                None => DRAIN_QUIET,
+            };
+
+            match timeout(quiet, self.stream.read(&mut chunk)).await {
+                Ok(Ok(0)) | Err(_) => break,
+                Ok(Ok(len)) => trace!("Discarding {} stale bytes", len),
+                Ok(Err(e)) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn exchange<D, I>(&mut self, mut codec: D, req: I) -> Result<D::Item>
+    where
+        D: Decoder<Error = Error> + Encoder<I, Error = Error>,
+    {
         // TODO: Find a way to use the `Framed` facility.
 
         // Encode the message.
-        let mut codec = Codec::<C>::new();
-
         let mut buf = BytesMut::new();
         codec.encode(req, &mut buf)?;
 
@@ -51,7 +317,7 @@ where
             self.buffer_in.reserve(1024);
             let len = self.stream.read_buf(&mut self.buffer_in).await?;
             if len == 0 {
-                return Err(Error::Io(ErrorKind::UnexpectedEof.into()));
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
             }
 
             // trace!("Read {} bytes from stream", len);
@@ -63,3 +329,35 @@ where
         })
     }
 }
+
+pub(crate) fn check_supported<C: Command>(capabilities: Option<&Capabilities>) -> Result<()> {
+    match capabilities {
+        Some(capabilities) if !capabilities.supports::<C>() => {
+            Err(Error::new(ErrorKind::UnsupportedCommand).with_command(C::COMMAND_NAME))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Protocol id from the answer to `QPI`, or `None` when the device rejected or ignored it.
+pub(crate) fn pi30_protocol_id(res: Result<QPIResponse>) -> Result<Option<u64>> {
+    match res {
+        Err(e) if matches!(e.kind(), ErrorKind::Io(e) if e.kind() == io::ErrorKind::TimedOut) => {
+            debug!("Command {} timed out", QPI::COMMAND_NAME);
+            Ok(None)
+        }
+        res => Ok(optional::<QPI>(res)?.map(|x| x.protocol_id)),
+    }
+}
+
+// Treat anything but a stream error as the command not being supported.
+pub(crate) fn optional<C: Command>(res: Result<C::Response>) -> Result<Option<C::Response>> {
+    match res {
+        Ok(x) => Ok(Some(x)),
+        Err(e) if matches!(e.kind(), ErrorKind::Io(_)) => Err(e),
+        Err(e) => {
+            debug!("Command {} not supported: {:?}", C::COMMAND_NAME, e);
+            Ok(None)
+        }
+    }
+}
@@ -1,5 +1,55 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod backend;
+#[cfg(feature = "std")]
+pub mod blocking;
+#[cfg(feature = "std")]
+pub mod bms;
+#[cfg(feature = "alloc")]
+pub mod capabilities;
+#[cfg(feature = "std")]
 pub mod codec;
+#[cfg(feature = "alloc")]
 pub mod command;
+#[cfg(feature = "alloc")]
 pub mod commands;
+#[cfg(feature = "alloc")]
+pub mod datetime;
 pub mod error;
+#[cfg(feature = "alloc")]
+pub mod events;
+#[cfg(feature = "std")]
+pub mod executor;
+#[cfg(feature = "alloc")]
+pub mod fault;
+#[cfg(feature = "alloc")]
+pub mod firmware;
+pub mod frame;
+#[cfg(feature = "std")]
+pub mod handle;
+#[cfg(feature = "alloc")]
+pub mod identity;
+#[cfg(feature = "std")]
 pub mod inverter;
+#[cfg(feature = "std")]
+pub mod modbus;
+#[cfg(feature = "alloc")]
+pub mod pi17;
+#[cfg(feature = "alloc")]
+pub mod pi18;
+#[cfg(feature = "alloc")]
+pub mod pv;
+#[cfg(feature = "alloc")]
+pub mod settings;
+#[cfg(feature = "alloc")]
+pub mod status;
+
+#[cfg(feature = "alloc")]
+mod tokenizer;
+
+#[cfg(test)]
+mod mock;
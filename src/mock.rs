@@ -0,0 +1,266 @@
+use crc_any::CRCu16;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Scripted stand-in for an inverter serial link.
+///
+/// Every complete request written to the stream is answered with the payload registered for it,
+/// or with a NAK if there is none. Ignored requests get no answer, and reads then block like those
+/// of a silent serial port: the async ones never complete and the blocking ones time out.
+#[derive(Default)]
+pub(crate) struct MockStream {
+    responses: HashMap<Vec<u8>, Vec<u8>>,
+    ignored: HashSet<Vec<u8>>,
+    silent: bool,
+    request_buffer: Vec<u8>,
+    response_buffer: VecDeque<u8>,
+    pub(crate) requests: Vec<Vec<u8>>,
+}
+
+impl MockStream {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn respond(mut self, request: &[u8], payload: &[u8]) -> Self {
+        self.responses.insert(request.to_vec(), payload.to_vec());
+        self
+    }
+
+    pub(crate) fn ignore(mut self, request: &[u8]) -> Self {
+        self.ignored.insert(request.to_vec());
+        self
+    }
+
+    fn process(&mut self, buf: &[u8]) {
+        for &byte in buf {
+            self.request_buffer.push(byte);
+            // A carriage return within the CRC sum doesn't end the request.
+            if byte != b'\r' || !has_valid_crc(&self.request_buffer) {
+                continue;
+            }
+
+            // Strip the CRC sum and the carriage return.
+            let request = std::mem::take(&mut self.request_buffer);
+            let request = request[..request.len().saturating_sub(3)].to_vec();
+
+            self.silent = self.ignored.contains(&request);
+            if self.silent {
+                self.requests.push(request);
+                continue;
+            }
+
+            // Requests starting with `^` are answered using PI17 framing.
+            let response = match (self.responses.get(&request), request.first()) {
+                (Some(payload), Some(b'^')) => frame_pi17(b'D', payload),
+                (None, Some(b'^')) => frame_pi17(b'0', b""),
+                (Some(payload), _) => frame(payload),
+                (None, _) => frame(b"NAK"),
+            };
+            self.response_buffer.extend(response);
+            self.requests.push(request);
+        }
+    }
+}
+
+fn has_valid_crc(request: &[u8]) -> bool {
+    if request.len() < 3 {
+        return false;
+    }
+
+    let (body, crc) = request[..request.len() - 1].split_at(request.len() - 3);
+    let mut crc_sum = CRCu16::crc16xmodem();
+    crc_sum.digest(body);
+    crc_sum.get_crc().to_be_bytes() == crc
+}
+
+pub(crate) fn frame(payload: &[u8]) -> Vec<u8> {
+    let mut res = vec![b'('];
+    res.extend_from_slice(payload);
+
+    let mut crc_sum = CRCu16::crc16xmodem();
+    crc_sum.digest(res.as_slice());
+    res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+    res.push(b'\r');
+
+    res
+}
+
+pub(crate) fn frame_pi17(frame_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut res = vec![b'^', frame_type];
+    if frame_type == b'D' {
+        res.extend_from_slice(format!("{:03}", payload.len() + 3).as_bytes());
+        res.extend_from_slice(payload);
+    }
+
+    let mut crc_sum = CRCu16::crc16xmodem();
+    crc_sum.digest(res.as_slice());
+    res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+    res.push(b'\r');
+
+    res
+}
+
+impl io::Read for MockStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.silent && self.response_buffer.is_empty() {
+            return Err(io::Error::from(io::ErrorKind::TimedOut));
+        }
+
+        let len = buf.len().min(self.response_buffer.len());
+        for (dst, src) in buf.iter_mut().zip(self.response_buffer.drain(..len)) {
+            *dst = src;
+        }
+
+        Ok(len)
+    }
+}
+
+impl io::Write for MockStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.process(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncRead for MockStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.silent && this.response_buffer.is_empty() {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(io::Read::read(this, buf))
+    }
+}
+
+impl AsyncWrite for MockStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(io::Write::write(self.get_mut(), buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Stand-in for a Modbus RTU slave holding a set of registers.
+///
+/// Reads of unset registers and unknown function codes are answered with exceptions.
+pub(crate) struct MockSlave {
+    slave: u8,
+    pub(crate) registers: HashMap<u16, u16>,
+    request_buffer: Vec<u8>,
+    response_buffer: VecDeque<u8>,
+}
+
+impl MockSlave {
+    pub(crate) fn new(slave: u8) -> Self {
+        Self {
+            slave,
+            registers: HashMap::new(),
+            request_buffer: Vec::new(),
+            response_buffer: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn with_registers(mut self, start: u16, values: &[u16]) -> Self {
+        for (address, value) in (start..).zip(values) {
+            self.registers.insert(address, *value);
+        }
+        self
+    }
+
+    fn process(&mut self, buf: &[u8]) {
+        self.request_buffer.extend_from_slice(buf);
+
+        // Both supported requests are eight bytes long.
+        while self.request_buffer.len() >= 8 {
+            let request = self.request_buffer.drain(..8).collect::<Vec<_>>();
+            let address = u16::from_be_bytes([request[2], request[3]]);
+            let value = u16::from_be_bytes([request[4], request[5]]);
+
+            let mut res = vec![self.slave, request[1]];
+            match request[1] {
+                0x03 => {
+                    let registers = (address..address + value)
+                        .map(|x| self.registers.get(&x).copied())
+                        .collect::<Option<Vec<_>>>();
+                    match registers {
+                        Some(registers) => {
+                            res.push(registers.len() as u8 * 2);
+                            for register in registers {
+                                res.extend_from_slice(&register.to_be_bytes());
+                            }
+                        }
+                        None => res = vec![self.slave, 0x83, 0x02],
+                    }
+                }
+                0x06 => {
+                    self.registers.insert(address, value);
+                    res.extend_from_slice(&request[2..6]);
+                }
+                x => res = vec![self.slave, x | 0x80, 0x01],
+            }
+
+            let mut crc_sum = CRCu16::crc16modbus();
+            crc_sum.digest(res.as_slice());
+            res.extend_from_slice(crc_sum.get_crc().to_le_bytes().as_ref());
+            self.response_buffer.extend(res);
+        }
+    }
+}
+
+impl AsyncRead for MockSlave {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let len = buf.len().min(this.response_buffer.len());
+        for (dst, src) in buf.iter_mut().zip(this.response_buffer.drain(..len)) {
+            *dst = src;
+        }
+
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl AsyncWrite for MockSlave {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().process(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
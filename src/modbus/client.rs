@@ -0,0 +1,232 @@
+use crate::commands::qmod::QMODResponse;
+use crate::commands::qpigs::QPIGSResponse;
+use crate::commands::qpiri::QPIRIResponse;
+use crate::error::{Error, ErrorKind, Result};
+use crate::inverter::Inverter;
+use crate::modbus::codec::{Codec, Request, Response};
+use crate::modbus::registers;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+pub struct Client<S> {
+    inverter: Inverter<S>,
+    slave: u8,
+    lenient_decoding: bool,
+}
+
+impl<S> Client<S> {
+    pub fn from_stream(stream: S, slave: u8) -> Self {
+        Self {
+            inverter: Inverter::from_stream(stream),
+            slave,
+            lenient_decoding: false,
+        }
+    }
+
+    /// Keep codes unknown to the decoders as `Unknown` enum variants, see
+    /// `Response::decode_lenient`.
+    pub fn with_lenient_decoding(mut self, lenient_decoding: bool) -> Self {
+        self.lenient_decoding = lenient_decoding;
+        self
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inverter.into_inner()
+    }
+}
+
+impl<S> Client<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    pub async fn read_holding_registers(&mut self, address: u16, count: u16) -> Result<Vec<u16>> {
+        let req = Request::ReadHoldingRegisters { address, count };
+        match self.inverter.transact(Codec::new(self.slave), req).await? {
+            Response::Registers(registers) if registers.len() == count as usize => Ok(registers),
+            _ => Err(Error::new(ErrorKind::InvalidResponseFormat)),
+        }
+    }
+
+    pub async fn write_single_register(&mut self, address: u16, value: u16) -> Result<()> {
+        let req = Request::WriteSingleRegister { address, value };
+        match self.inverter.transact(Codec::new(self.slave), req).await? {
+            Response::RegisterWritten {
+                address: written_address,
+                value: written_value,
+            } if written_address == address && written_value == value => Ok(()),
+            _ => Err(Error::new(ErrorKind::InvalidResponseFormat)),
+        }
+    }
+
+    pub async fn query_general_status(&mut self) -> Result<QPIGSResponse> {
+        let status = self
+            .read_holding_registers(registers::STATUS_START, registers::STATUS_COUNT)
+            .await?;
+        registers::general_status(&status)
+    }
+
+    pub async fn query_device_mode(&mut self) -> Result<QMODResponse> {
+        let status = self
+            .read_holding_registers(registers::WORKING_MODE, 1)
+            .await?;
+        if self.lenient_decoding {
+            registers::device_mode_lenient(&status)
+        } else {
+            registers::device_mode(&status)
+        }
+    }
+
+    pub async fn query_rating_information(&mut self) -> Result<QPIRIResponse> {
+        let settings = self
+            .read_holding_registers(registers::SETTINGS_START, registers::SETTINGS_COUNT)
+            .await?;
+        if self.lenient_decoding {
+            registers::rating_information_lenient(&settings)
+        } else {
+            registers::rating_information(&settings)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::commands::qmod::DeviceMode;
+    use crate::commands::qpigs::DeviceChargingStatus;
+    use crate::commands::qpiri::{BatteryType, ChargeSourcePriority, OutputSourcePriority};
+    use crate::error::{ErrorKind, Result};
+    use crate::mock::MockSlave;
+    use crate::modbus::client::Client;
+    use crate::modbus::registers;
+
+    fn slave() -> MockSlave {
+        let mut status = [0u16; registers::STATUS_COUNT as usize];
+        status[0] = 3; // Off-grid
+        status[1] = 2301;
+        status[2] = 4998;
+        status[9] = 2299;
+        status[11] = 5000;
+        status[12] = 450;
+        status[13] = 520;
+        status[14] = 532;
+        status[15] = (-125i16) as u16;
+        status[18] = 3205;
+        status[19] = 42;
+        status[23] = 1300;
+        status[24] = 9;
+        status[26] = 41;
+        status[28] = 85;
+
+        let mut settings = [0u16; registers::SETTINGS_COUNT as usize];
+        settings[1] = 2;
+        settings[20] = 2300;
+        settings[21] = 5000;
+        settings[22] = 2;
+        settings[24] = 564;
+        settings[25] = 540;
+        settings[26] = 520;
+        settings[27] = 460;
+        settings[29] = 420;
+        settings[31] = 3;
+        settings[32] = 800;
+        settings[33] = 300;
+
+        MockSlave::new(1)
+            .with_registers(registers::STATUS_START, &status)
+            .with_registers(registers::SETTINGS_START, &settings)
+    }
+
+    #[tokio::test]
+    async fn test_query_general_status() -> Result<()> {
+        let mut client = Client::from_stream(slave(), 1);
+
+        let item = client.query_general_status().await?;
+        assert_eq!(item.grid_voltage, 230.1);
+        assert_eq!(item.grid_frequency, 49.98);
+        assert_eq!(item.ac_out_active_power, 450);
+        assert_eq!(item.battery_voltage, 53.2);
+        assert_eq!(item.battery_charge_current, 0);
+        assert_eq!(item.battery_discharge_current, 12);
+        assert_eq!(item.battery_capacity, 85);
+        assert_eq!(item.pv_input_voltage, 320.5);
+        assert_eq!(
+            item.device_status.charge_status,
+            DeviceChargingStatus::ChargingFromSCC
+        );
+
+        let item = client.query_device_mode().await?;
+        assert_eq!(item.mode, DeviceMode::BatteryMode);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_rating_information() -> Result<()> {
+        let mut client = Client::from_stream(slave(), 1);
+
+        let item = client.query_rating_information().await?;
+        assert_eq!(item.ac_output_rating_voltage, 230.0);
+        assert_eq!(item.ac_out_rating_frequency, 50.0);
+        assert_eq!(item.battery_bulk_voltage, 56.4);
+        assert_eq!(item.battery_under_voltage, 42.0);
+        assert_eq!(item.battery_type, BatteryType::User);
+        assert_eq!(item.max_charging_current, 80);
+        assert_eq!(item.max_ac_charging_current, 30);
+        assert_eq!(item.output_source_priority, OutputSourcePriority::SBUFirst);
+        assert_eq!(item.charge_source_priority, ChargeSourcePriority::OnlySolar);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_lenient_decoding() -> Result<()> {
+        let mut slave = slave();
+        slave.registers.insert(registers::WORKING_MODE, 9);
+        slave.registers.insert(registers::BATTERY_TYPE, 4);
+        slave.registers.insert(registers::CHARGE_PRIORITY, 7);
+        let mut client = Client::from_stream(slave, 1);
+
+        let err = client.query_device_mode().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidDeviceMode));
+        let err = client.query_rating_information().await.unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::InvalidDeviceChargeSourcePriority
+        ));
+
+        let mut client = client.with_lenient_decoding(true);
+        let item = client.query_device_mode().await?;
+        assert_eq!(item.mode, DeviceMode::Unknown(String::from("9")));
+        let item = client.query_rating_information().await?;
+        assert_eq!(item.battery_type, BatteryType::Pylontech);
+        assert_eq!(
+            item.charge_source_priority,
+            ChargeSourcePriority::Unknown(String::from("7"))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_single_register() -> Result<()> {
+        let mut client = Client::from_stream(slave(), 1);
+
+        client
+            .write_single_register(registers::OUTPUT_PRIORITY, 1)
+            .await?;
+        let item = client.query_rating_information().await?;
+        assert_eq!(
+            item.output_source_priority,
+            OutputSourcePriority::SolarFirst
+        );
+
+        assert!(matches!(
+            client
+                .read_holding_registers(100, 2)
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ModbusException(2)
+        ));
+
+        Ok(())
+    }
+}
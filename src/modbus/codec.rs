@@ -0,0 +1,203 @@
+use crate::error::{Error, ErrorKind, Result};
+use bytes::{Buf, BufMut, BytesMut};
+use crc_any::CRCu16;
+use log::{debug, trace};
+use tokio_util::codec::{Decoder, Encoder};
+
+const READ_HOLDING_REGISTERS: u8 = 0x03;
+const WRITE_SINGLE_REGISTER: u8 = 0x06;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Request {
+    ReadHoldingRegisters { address: u16, count: u16 },
+    WriteSingleRegister { address: u16, value: u16 },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Response {
+    Registers(Vec<u16>),
+    RegisterWritten { address: u16, value: u16 },
+}
+
+// Modbus RTU frames are `slave, function, data..., CRC` with the CRC16-Modbus sum in little
+// endian. There is no frame terminator, so the frame length is derived from the function code.
+pub struct Codec {
+    slave: u8,
+}
+
+impl Codec {
+    pub fn new(slave: u8) -> Self {
+        Self { slave }
+    }
+
+    pub(crate) fn compute_crc(data: &[u8]) -> u16 {
+        let mut computed_crc_sum = CRCu16::crc16modbus();
+        computed_crc_sum.digest(data);
+
+        computed_crc_sum.get_crc()
+    }
+}
+
+impl Decoder for Codec {
+    type Item = Response;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        if src.len() < 3 {
+            return Ok(None);
+        }
+
+        let function = src[1];
+        let frame_len = match function {
+            READ_HOLDING_REGISTERS => 5 + src[2] as usize,
+            WRITE_SINGLE_REGISTER => 8,
+            x if x & 0x80 != 0 => 5,
+            _ => {
+                trace!("Invalid Modbus response: {:?}", &src[..]);
+                let err = Error::new(ErrorKind::InvalidResponseFormat).with_frame(src);
+                src.advance(src.len());
+                return Err(err);
+            }
+        };
+
+        if src.len() < frame_len {
+            return Ok(None);
+        }
+
+        let item = src.split_to(frame_len);
+        trace!("Decoding Modbus response: {:?}.", &item[..]);
+
+        let crc_sum = Self::compute_crc(&item[..frame_len - 2]);
+        if crc_sum != (&item[frame_len - 2..]).get_u16_le() {
+            return Err(Error::new(ErrorKind::InvalidResponseCrcSum).with_frame(&item));
+        }
+
+        if item[0] != self.slave {
+            return Err(Error::new(ErrorKind::InvalidResponsePrefix).with_frame(&item));
+        }
+
+        let mut data = &item[2..frame_len - 2];
+        Ok(Some(match function {
+            READ_HOLDING_REGISTERS => {
+                // Skip the byte count.
+                data.advance(1);
+                let mut registers = Vec::with_capacity(data.len() / 2);
+                while data.remaining() >= 2 {
+                    registers.push(data.get_u16());
+                }
+
+                Response::Registers(registers)
+            }
+            WRITE_SINGLE_REGISTER => Response::RegisterWritten {
+                address: data.get_u16(),
+                value: data.get_u16(),
+            },
+            _ => {
+                debug!("Modbus exception {:#04x}.", item[2]);
+                return Err(Error::new(ErrorKind::ModbusException(item[2])).with_frame(&item));
+            }
+        }))
+    }
+}
+
+impl Encoder<Request> for Codec {
+    type Error = Error;
+
+    fn encode(&mut self, item: Request, dst: &mut BytesMut) -> Result<()> {
+        // Note: Used for logging.
+        let start_len = dst.len();
+
+        trace!("Modbus request: {:?}.", item);
+
+        dst.put_u8(self.slave);
+        match item {
+            Request::ReadHoldingRegisters { address, count } => {
+                dst.put_u8(READ_HOLDING_REGISTERS);
+                dst.put_u16(address);
+                dst.put_u16(count);
+            }
+            Request::WriteSingleRegister { address, value } => {
+                dst.put_u8(WRITE_SINGLE_REGISTER);
+                dst.put_u16(address);
+                dst.put_u16(value);
+            }
+        }
+
+        let crc_sum = Self::compute_crc(&dst[start_len..]);
+        dst.put_u16_le(crc_sum);
+
+        trace!("Encoded Modbus request: {:?}", &dst[start_len..]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{ErrorKind, Result};
+    use crate::modbus::codec::{Codec, Request, Response};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_encode_read_holding_registers() -> Result<()> {
+        let mut codec = Codec::new(1);
+
+        let mut buf = BytesMut::new();
+        codec.encode(
+            Request::ReadHoldingRegisters {
+                address: 0x006b,
+                count: 3,
+            },
+            &mut buf,
+        )?;
+
+        assert_eq!(buf.bytes(), b"\x01\x03\x00\x6b\x00\x03\x74\x17");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_registers() -> Result<()> {
+        let mut codec = Codec::new(1);
+
+        let mut buf = BytesMut::from(&b"\x01\x03\x04\x00\x0a\x01\x02"[..]);
+        assert_eq!(codec.decode(&mut buf)?, None);
+
+        let crc_sum = Codec::compute_crc(&buf);
+        buf.extend_from_slice(&crc_sum.to_le_bytes());
+        assert_eq!(
+            codec.decode(&mut buf)?,
+            Some(Response::Registers(vec![0x000a, 0x0102]))
+        );
+        assert_eq!(buf.remaining(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_exception() {
+        let mut codec = Codec::new(1);
+
+        let mut buf = BytesMut::from(&b"\x01\x83\x02"[..]);
+        let crc_sum = Codec::compute_crc(&buf);
+        buf.extend_from_slice(&crc_sum.to_le_bytes());
+
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap_err().kind(),
+            ErrorKind::ModbusException(2)
+        ));
+    }
+
+    #[test]
+    fn test_decode_invalid_crc() {
+        let mut codec = Codec::new(1);
+
+        let mut buf = BytesMut::from(&b"\x01\x06\x01\x2c\x00\x01\x00\x00"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap_err().kind(),
+            ErrorKind::InvalidResponseCrcSum
+        ));
+        assert_eq!(buf.remaining(), 0);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::commands::qmod::DeviceMode::{
+    BatteryMode, FaultMode, LineMode, PowerOnMode, StandbyMode,
+};
+use crate::commands::qmod::{DeviceMode, QMODResponse};
+use crate::commands::qpigs::DeviceChargingStatus::{
+    ChargingFromAC, ChargingFromSCC, ChargingFromSCCAndAC, NotCharging,
+};
+use crate::commands::qpigs::{DeviceStatus, QPIGSResponse};
+use crate::commands::qpiri::ChargeSourcePriority::{
+    GridFirst as ChargeSourceGridFirst, OnlySolar, SolarAndGrid,
+    SolarFirst as ChargeSourceSolarFirst,
+};
+use crate::commands::qpiri::InputVoltageRange::{Appliance, UPS};
+use crate::commands::qpiri::MachineType::OffGrid;
+use crate::commands::qpiri::OutputMode::{
+    ParallelOutput, Phase1Of3Output, Phase2Of3Output, Phase3Of3Output, SingleMachineOutput,
+};
+use crate::commands::qpiri::OutputSourcePriority::{
+    GridFirst as OutputSourceGridFirst, SBUFirst, SolarFirst as OutputSourceSolarFirst,
+};
+use crate::commands::qpiri::Topology::Transformerless;
+use crate::commands::qpiri::{
+    BatteryType, ChargeSourcePriority, InputVoltageRange, OutputMode, OutputSourcePriority,
+    QPIRIResponse,
+};
+use crate::error::{Error, ErrorKind, Result};
+
+// Register map of the SMG family of Modbus-capable inverters.
+
+// Status block. Voltages and currents are in tenths, frequencies in hundredths of their unit.
+pub const STATUS_START: u16 = 201;
+pub const STATUS_COUNT: u16 = 29;
+
+pub const WORKING_MODE: u16 = 201;
+pub const GRID_VOLTAGE: u16 = 202;
+pub const GRID_FREQUENCY: u16 = 203;
+pub const GRID_CHARGING_POWER: u16 = 209;
+pub const OUTPUT_VOLTAGE: u16 = 210;
+pub const OUTPUT_FREQUENCY: u16 = 212;
+pub const OUTPUT_ACTIVE_POWER: u16 = 213;
+pub const OUTPUT_APPARENT_POWER: u16 = 214;
+pub const BATTERY_VOLTAGE: u16 = 215;
+// Signed, positive while charging.
+pub const BATTERY_CURRENT: u16 = 216;
+pub const PV_VOLTAGE: u16 = 219;
+pub const PV_CURRENT: u16 = 220;
+pub const PV_CHARGING_POWER: u16 = 224;
+pub const LOAD_PERCENT: u16 = 225;
+pub const INVERTER_TEMPERATURE: u16 = 227;
+pub const BATTERY_PERCENT: u16 = 229;
+
+// Settings block.
+pub const SETTINGS_START: u16 = 300;
+pub const SETTINGS_COUNT: u16 = 34;
+
+pub const OUTPUT_MODE: u16 = 300;
+pub const OUTPUT_PRIORITY: u16 = 301;
+pub const INPUT_VOLTAGE_RANGE: u16 = 302;
+pub const OUTPUT_VOLTAGE_SETTING: u16 = 320;
+pub const OUTPUT_FREQUENCY_SETTING: u16 = 321;
+pub const BATTERY_TYPE: u16 = 322;
+pub const BATTERY_BULK_VOLTAGE: u16 = 324;
+pub const BATTERY_FLOAT_VOLTAGE: u16 = 325;
+pub const BATTERY_REDISCHARGE_VOLTAGE: u16 = 326;
+pub const BATTERY_RECHARGE_VOLTAGE: u16 = 327;
+pub const BATTERY_UNDER_VOLTAGE: u16 = 329;
+pub const CHARGE_PRIORITY: u16 = 331;
+pub const MAX_CHARGING_CURRENT: u16 = 332;
+pub const MAX_AC_CHARGING_CURRENT: u16 = 333;
+
+// Read a register from a block that was read starting at `start`.
+fn register(block: &[u16], start: u16, address: u16) -> Result<u16> {
+    address
+        .checked_sub(start)
+        .and_then(|x| block.get(x as usize))
+        .copied()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidPayload))
+}
+
+fn tenths(block: &[u16], start: u16, address: u16) -> Result<f32> {
+    Ok(register(block, start, address)? as f32 / 10.0)
+}
+
+fn hundredths(block: &[u16], start: u16, address: u16) -> Result<f32> {
+    Ok(register(block, start, address)? as f32 / 100.0)
+}
+
+pub fn device_mode(status: &[u16]) -> Result<QMODResponse> {
+    decode_device_mode(status, false)
+}
+
+/// Like `device_mode`, keeping unknown codes as `Unknown` variants, see `Response::decode_lenient`.
+pub fn device_mode_lenient(status: &[u16]) -> Result<QMODResponse> {
+    decode_device_mode(status, true)
+}
+
+fn decode_device_mode(status: &[u16], lenient: bool) -> Result<QMODResponse> {
+    Ok(QMODResponse {
+        mode: match register(status, STATUS_START, WORKING_MODE)? {
+            0 => PowerOnMode,
+            1 => StandbyMode,
+            2 => LineMode,
+            3 => BatteryMode,
+            4 => LineMode,
+            // Charging without output.
+            5 => StandbyMode,
+            6 => FaultMode,
+            x if lenient => DeviceMode::Unknown(x.to_string()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceMode,
+                    "mode",
+                    x.to_string().as_bytes(),
+                ))
+            }
+        },
+    })
+}
+
+// The register map has no bus voltage nor a separate SCC battery voltage, so the bus voltage is
+// reported as zero and the SCC voltage as the battery voltage.
+pub fn general_status(status: &[u16]) -> Result<QPIGSResponse> {
+    // Widened so that negating the lowest reading can't overflow.
+    let battery_current = register(status, STATUS_START, BATTERY_CURRENT)? as i16 as i32;
+    let battery_voltage = tenths(status, STATUS_START, BATTERY_VOLTAGE)?;
+    let ac_out_active_power = register(status, STATUS_START, OUTPUT_ACTIVE_POWER)? as usize;
+
+    let charging_from_ac = register(status, STATUS_START, GRID_CHARGING_POWER)? > 0;
+    let pv_charging_power = register(status, STATUS_START, PV_CHARGING_POWER)? as usize;
+    let charging_from_scc = pv_charging_power > 0;
+
+    Ok(QPIGSResponse {
+        grid_voltage: tenths(status, STATUS_START, GRID_VOLTAGE)?,
+        grid_frequency: hundredths(status, STATUS_START, GRID_FREQUENCY)?,
+        ac_out_voltage: tenths(status, STATUS_START, OUTPUT_VOLTAGE)?,
+        ac_out_frequency: hundredths(status, STATUS_START, OUTPUT_FREQUENCY)?,
+        ac_out_apparent_power: register(status, STATUS_START, OUTPUT_APPARENT_POWER)? as usize,
+        ac_out_active_power,
+        out_load_percent: register(status, STATUS_START, LOAD_PERCENT)? as usize,
+        bus_voltage: 0,
+        battery_voltage,
+        battery_charge_current: battery_current.max(0) as usize / 10,
+        battery_capacity: register(status, STATUS_START, BATTERY_PERCENT)? as usize,
+        inverter_heat_sink_temp: register(status, STATUS_START, INVERTER_TEMPERATURE)? as usize,
+        pv_input_current: register(status, STATUS_START, PV_CURRENT)? as usize / 10,
+        pv_input_voltage: tenths(status, STATUS_START, PV_VOLTAGE)?,
+        battery_scc_voltage: battery_voltage,
+        battery_discharge_current: (-battery_current).max(0) as usize / 10,
+        device_status: DeviceStatus {
+            charge_status: match (charging_from_scc, charging_from_ac) {
+                (false, false) => NotCharging,
+                (true, false) => ChargingFromSCC,
+                (false, true) => ChargingFromAC,
+                (true, true) => ChargingFromSCCAndAC,
+            },
+            active_load: ac_out_active_power > 0,
+        },
+        pv_charging_power: Some(pv_charging_power),
+    })
+}
+
+// The register map has no rated values, so those are taken from the output settings where they
+// exist and reported as zero otherwise. These models are all transformerless off-grid units.
+pub fn rating_information(settings: &[u16]) -> Result<QPIRIResponse> {
+    decode_rating_information(settings, false)
+}
+
+/// Like `rating_information`, keeping unknown codes as `Unknown` variants, see
+/// `Response::decode_lenient`.
+pub fn rating_information_lenient(settings: &[u16]) -> Result<QPIRIResponse> {
+    decode_rating_information(settings, true)
+}
+
+fn decode_rating_information(settings: &[u16], lenient: bool) -> Result<QPIRIResponse> {
+    Ok(QPIRIResponse {
+        grid_rating_voltage: 0.0,
+        grid_rating_current: 0.0,
+        ac_output_rating_voltage: tenths(settings, SETTINGS_START, OUTPUT_VOLTAGE_SETTING)?,
+        ac_out_rating_frequency: hundredths(settings, SETTINGS_START, OUTPUT_FREQUENCY_SETTING)?,
+        ac_out_rating_current: 0.0,
+        ac_out_rating_apparent_power: 0,
+        ac_out_rating_active_power: 0,
+        battery_rating_voltage: 0.0,
+        battery_recharge_voltage: tenths(settings, SETTINGS_START, BATTERY_RECHARGE_VOLTAGE)?,
+        battery_under_voltage: tenths(settings, SETTINGS_START, BATTERY_UNDER_VOLTAGE)?,
+        battery_bulk_voltage: tenths(settings, SETTINGS_START, BATTERY_BULK_VOLTAGE)?,
+        battery_float_voltage: tenths(settings, SETTINGS_START, BATTERY_FLOAT_VOLTAGE)?,
+        // The same codes as PI30, lithium profiles included.
+        battery_type: BatteryType::decode(
+            &register(settings, SETTINGS_START, BATTERY_TYPE)?.to_string(),
+            lenient,
+        )?,
+        max_ac_charging_current: register(settings, SETTINGS_START, MAX_AC_CHARGING_CURRENT)?
+            as i32
+            / 10,
+        max_charging_current: register(settings, SETTINGS_START, MAX_CHARGING_CURRENT)? as i32 / 10,
+        input_voltage_range: match register(settings, SETTINGS_START, INPUT_VOLTAGE_RANGE)? {
+            0 => Appliance,
+            1 => UPS,
+            x if lenient => InputVoltageRange::Unknown(x.to_string()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceInputVoltageRange,
+                    "input_voltage_range",
+                    x.to_string().as_bytes(),
+                ))
+            }
+        },
+        output_source_priority: match register(settings, SETTINGS_START, OUTPUT_PRIORITY)? {
+            0 => OutputSourceGridFirst,
+            1 => OutputSourceSolarFirst,
+            2 => SBUFirst,
+            x if lenient => OutputSourcePriority::Unknown(x.to_string()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceOutputSourcePriority,
+                    "output_source_priority",
+                    x.to_string().as_bytes(),
+                ))
+            }
+        },
+        charge_source_priority: match register(settings, SETTINGS_START, CHARGE_PRIORITY)? {
+            0 => ChargeSourceGridFirst,
+            1 => ChargeSourceSolarFirst,
+            2 => SolarAndGrid,
+            3 => OnlySolar,
+            x if lenient => ChargeSourcePriority::Unknown(x.to_string()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceChargeSourcePriority,
+                    "charge_source_priority",
+                    x.to_string().as_bytes(),
+                ))
+            }
+        },
+        machine_type: OffGrid,
+        topology: Transformerless,
+        output_mode: match register(settings, SETTINGS_START, OUTPUT_MODE)? {
+            0 => SingleMachineOutput,
+            1 => ParallelOutput,
+            2 => Phase1Of3Output,
+            3 => Phase2Of3Output,
+            4 => Phase3Of3Output,
+            x if lenient => OutputMode::Unknown(x.to_string()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceOutputMode,
+                    "output_mode",
+                    x.to_string().as_bytes(),
+                ))
+            }
+        },
+        battery_redischarge_voltage: tenths(settings, SETTINGS_START, BATTERY_REDISCHARGE_VOLTAGE)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ErrorKind;
+    use crate::modbus::registers::{register, STATUS_START, WORKING_MODE};
+
+    #[test]
+    fn test_register_outside_block() {
+        let block = [3, 2301];
+        assert_eq!(register(&block, STATUS_START, WORKING_MODE).unwrap(), 3);
+
+        for address in &[STATUS_START - 1, STATUS_START + 2] {
+            let err = register(&block, STATUS_START, *address).unwrap_err();
+            assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+#[cfg(feature = "std")]
+pub mod codec;
+pub mod commands;
@@ -0,0 +1,203 @@
+use crate::command::Command;
+use crate::error::{Error, Result};
+use crate::frame;
+use bytes::{Buf, BufMut, BytesMut};
+use log::{debug, trace};
+use std::marker::PhantomData;
+use tokio_util::codec::{Decoder, Encoder};
+
+// Requests are framed as `^P` (queries) or `^S` (setters), followed by the number of bytes left in
+// the frame (command, payload, CRC sum and carriage return) as three decimal digits. Responses are
+// framed the same way behind `^D`, except for setter replies which are a bare `^1` (ACK) or `^0`
+// (NAK) followed by the CRC sum.
+pub struct Codec<C> {
+    lenient_decoding: bool,
+    phantom: PhantomData<C>,
+}
+
+impl<C> Codec<C> {
+    pub fn new() -> Self {
+        Self {
+            lenient_decoding: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Decode responses with `Response::decode_lenient`.
+    pub fn with_lenient_decoding(mut self, lenient_decoding: bool) -> Self {
+        self.lenient_decoding = lenient_decoding;
+        self
+    }
+}
+
+impl<C> Default for Codec<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Decoder for Codec<C>
+where
+    C: Command,
+{
+    type Item = C::Response;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        let item = match frame::decode_pi17(src) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        debug!("Decoding response {}.", C::COMMAND_NAME);
+        trace!("Decoding response ({}): {:?}.", C::COMMAND_NAME, item.raw);
+
+        let consumed = item.consumed;
+        let res = if self.lenient_decoding {
+            item.decode_lenient::<C>()
+        } else {
+            item.decode::<C>()
+        };
+        src.advance(consumed);
+
+        let decoded_item = res?;
+        trace!("Decoded response ({}): {:?}", C::COMMAND_NAME, decoded_item);
+
+        Ok(Some(decoded_item))
+    }
+}
+
+impl<C> Encoder<C::Request> for Codec<C>
+where
+    C: Command,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: C::Request, dst: &mut BytesMut) -> Result<()> {
+        debug!("Encoding command {}.", C::COMMAND_NAME);
+        trace!("Command payload ({}): {:?}.", C::COMMAND_NAME, item);
+
+        let encoded = frame::encode_pi17::<C>(&item)?;
+        trace!("Encoded command ({}): {:?}", C::COMMAND_NAME, encoded);
+        dst.put_slice(&encoded);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::Ack;
+    use crate::commands::pbt::PBT;
+    use crate::error::{ErrorKind, Result};
+    use crate::pi17::codec::Codec;
+    use crate::pi17::commands::pi::{PIResponse, PI};
+    use bytes::{Buf, BytesMut};
+    use crc_any::CRCu16;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    fn frame(data: &str) -> BytesMut {
+        let mut res = data.to_owned().into_bytes();
+        let mut crc_sum = CRCu16::crc16xmodem();
+        crc_sum.digest(res.as_slice());
+        res.extend_from_slice(crc_sum.get_crc().to_be_bytes().as_ref());
+        res.push(b'\r');
+
+        BytesMut::from(res.as_slice())
+    }
+
+    #[test]
+    fn test_encode() -> Result<()> {
+        let mut codec = Codec::<PI>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(&buf[..7], b"^P005PI");
+        assert_eq!(buf.len(), 10);
+        assert_eq!(buf, frame("^P005PI"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode() -> Result<()> {
+        let mut codec = Codec::<PI>::new();
+
+        let mut buf = frame("^D00517");
+        assert_eq!(
+            codec.decode(&mut buf)?,
+            Some(PIResponse { protocol_id: 17 })
+        );
+        assert_eq!(buf.remaining(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_partial() -> Result<()> {
+        let mut codec = Codec::<PI>::new();
+
+        let full = frame("^D00517");
+        let mut buf = BytesMut::from(&full[..6]);
+        assert_eq!(codec.decode(&mut buf)?, None);
+
+        buf.extend_from_slice(&full[6..]);
+        assert_eq!(
+            codec.decode(&mut buf)?,
+            Some(PIResponse { protocol_id: 17 })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recover_invalid_format() -> Result<()> {
+        let mut codec = Codec::<PI>::new();
+
+        let mut buf = BytesMut::from(&b"\x00\x00\x00"[..]);
+        buf.extend_from_slice(&frame("^D00517"));
+        assert_eq!(
+            codec.decode(&mut buf)?,
+            Some(PIResponse { protocol_id: 17 })
+        );
+        assert_eq!(buf.remaining(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_invalid_crc() {
+        let mut codec = Codec::<PI>::new();
+
+        let mut buf = BytesMut::from(&b"^D00517\x00\x00\r"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap_err().kind(),
+            ErrorKind::InvalidResponseCrcSum
+        ));
+        assert_eq!(buf.remaining(), 0);
+    }
+
+    #[test]
+    fn test_decode_ack() -> Result<()> {
+        let mut codec = Codec::<PBT>::new();
+
+        let mut buf = frame("^1");
+        assert_eq!(codec.decode(&mut buf)?, Some(Ack));
+        assert_eq!(buf.remaining(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_nak() {
+        let mut codec = Codec::<PI>::new();
+
+        let mut buf = frame("^0");
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap_err().kind(),
+            ErrorKind::NotAcknowledged
+        ));
+        assert_eq!(buf.remaining(), 0);
+    }
+}
@@ -0,0 +1,8 @@
+// PI17 and PI18 responses may be followed by fields we don't know about yet, so decoders only
+// require the fields they read.
+pub mod et;
+pub mod gs;
+pub mod mode;
+pub mod pi;
+pub mod piri;
+pub mod ws;
@@ -0,0 +1,74 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct ET;
+
+impl Command for ET {
+    const PROTOCOL_ID: &'static [u8] = b"ET";
+    const COMMAND_NAME: &'static str = "PI17QueryTotalGeneratedEnergy";
+
+    type Request = ();
+    type Response = ETResponse;
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct ETResponse {
+    // Energy in kWh.
+    pub total_generated_energy: u64,
+}
+
+impl Response for ETResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 1..)?;
+
+        Ok(Self {
+            total_generated_energy: fields.parse("total_generated_energy")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::Result;
+    use crate::pi17::codec::Codec;
+    use crate::pi17::commands::et::{ETResponse, ET};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_et_payload_encode() -> Result<()> {
+        let req: <ET as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_et_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("00012345");
+        let item = <ET as Command>::Response::decode(&buf)?;
+
+        assert_eq!(
+            item,
+            ETResponse {
+                total_generated_energy: 12345
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_et_command_encode() -> Result<()> {
+        let mut codec = Codec::<ET>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P005ET\x4e\x91\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct GS;
+
+impl Command for GS {
+    const PROTOCOL_ID: &'static [u8] = b"GS";
+    const COMMAND_NAME: &'static str = "PI17QueryGeneralStatus";
+
+    type Request = ();
+    type Response = GSResponse;
+}
+
+// Three-phase values are reported as R, S and T phases. Single-phase units report zeros for S
+// and T.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GSResponse {
+    pub pv1_input_voltage: f32,
+    pub pv2_input_voltage: f32,
+    pub pv1_input_current: f32,
+    pub pv2_input_current: f32,
+    pub battery_voltage: f32,
+    pub battery_capacity: u32,
+    pub battery_current: f32,
+    pub grid_voltage: [f32; 3],
+    pub grid_frequency: f32,
+    pub grid_current: [f32; 3],
+    pub ac_out_voltage: [f32; 3],
+    pub ac_out_frequency: f32,
+    pub ac_out_current: [f32; 3],
+    pub inner_temperature: i32,
+    pub component_max_temperature: i32,
+    pub external_battery_temperature: i32,
+    pub setting_changed: bool,
+}
+
+impl Response for GSResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 25..)?;
+
+        Ok(Self {
+            pv1_input_voltage: fields.scaled("pv1_input_voltage", 10.0)?,
+            pv2_input_voltage: fields.scaled("pv2_input_voltage", 10.0)?,
+            pv1_input_current: fields.scaled("pv1_input_current", 10.0)?,
+            pv2_input_current: fields.scaled("pv2_input_current", 10.0)?,
+            battery_voltage: fields.scaled("battery_voltage", 10.0)?,
+            battery_capacity: fields.parse::<u32>("battery_capacity")?,
+            battery_current: fields.scaled("battery_current", 10.0)?,
+            grid_voltage: [
+                fields.scaled("grid_voltage", 10.0)?,
+                fields.scaled("grid_voltage", 10.0)?,
+                fields.scaled("grid_voltage", 10.0)?,
+            ],
+            grid_frequency: fields.scaled("grid_frequency", 100.0)?,
+            grid_current: [
+                fields.scaled("grid_current", 10.0)?,
+                fields.scaled("grid_current", 10.0)?,
+                fields.scaled("grid_current", 10.0)?,
+            ],
+            ac_out_voltage: [
+                fields.scaled("ac_out_voltage", 10.0)?,
+                fields.scaled("ac_out_voltage", 10.0)?,
+                fields.scaled("ac_out_voltage", 10.0)?,
+            ],
+            ac_out_frequency: fields.scaled("ac_out_frequency", 100.0)?,
+            ac_out_current: [
+                fields.scaled("ac_out_current", 10.0)?,
+                fields.scaled("ac_out_current", 10.0)?,
+                fields.scaled("ac_out_current", 10.0)?,
+            ],
+            inner_temperature: fields.parse::<i32>("inner_temperature")?,
+            component_max_temperature: fields.parse::<i32>("component_max_temperature")?,
+            external_battery_temperature: fields.parse::<i32>("external_battery_temperature")?,
+            setting_changed: fields.flag("setting_changed")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::{ErrorKind, Result};
+    use crate::pi17::codec::Codec;
+    use crate::pi17::commands::gs::{GSResponse, GS};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_gs_payload_encode() -> Result<()> {
+        let req: <GS as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gs_payload_decode_custom() -> Result<()> {
+        let res = "2356,0000,0042,0000,0532,080,-0125,2301,0000,0000,4998,0012,0000,0000,2299,0000,0000,5000,0031,0000,0000,035,041,000,0";
+
+        let buf = BytesMut::from(res);
+        let item = <GS as Command>::Response::decode(&buf)?;
+        assert_eq!(
+            item,
+            GSResponse {
+                pv1_input_voltage: 235.6,
+                pv2_input_voltage: 0.0,
+                pv1_input_current: 4.2,
+                pv2_input_current: 0.0,
+                battery_voltage: 53.2,
+                battery_capacity: 80,
+                battery_current: -12.5,
+                grid_voltage: [230.1, 0.0, 0.0],
+                grid_frequency: 49.98,
+                grid_current: [1.2, 0.0, 0.0],
+                ac_out_voltage: [229.9, 0.0, 0.0],
+                ac_out_frequency: 50.0,
+                ac_out_current: [3.1, 0.0, 0.0],
+                inner_temperature: 35,
+                component_max_temperature: 41,
+                external_battery_temperature: 0,
+                setting_changed: false,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gs_payload_decode_short() {
+        let buf = BytesMut::from("2356,0000,0042");
+        assert!(<GS as Command>::Response::decode(&buf).is_err());
+    }
+
+    #[test]
+    fn test_gs_payload_decode_invalid_flag() {
+        let res = "2356,0000,0042,0000,0532,080,-0125,2301,0000,0000,4998,0012,0000,0000,2299,0000,0000,5000,0031,0000,0000,035,041,000,X";
+        let err = <GS as Command>::Response::decode(res.as_bytes()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+        assert_eq!(err.field(), Some("setting_changed"));
+    }
+
+    #[test]
+    fn test_gs_command_encode() -> Result<()> {
+        let mut codec = Codec::<GS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P005GS\x58\x14\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::command::{Command, Response};
+use crate::commands::qmod::DeviceMode;
+use crate::commands::qmod::DeviceMode::{
+    BatteryMode, FaultMode, LineMode, PowerOnMode, StandbyMode,
+};
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use serde_derive::Serialize;
+
+pub struct MOD;
+
+impl Command for MOD {
+    const PROTOCOL_ID: &'static [u8] = b"MOD";
+    const COMMAND_NAME: &'static str = "PI17QueryWorkingMode";
+
+    type Request = ();
+    type Response = MODResponse;
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct MODResponse {
+    pub mode: DeviceMode,
+}
+
+impl Response for MODResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl MODResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 1..)?;
+
+        Ok(Self {
+            // Bypass and hybrid modes are both reported as line mode.
+            mode: match fields.str("mode")? {
+                "00" => PowerOnMode,
+                "01" => StandbyMode,
+                "02" => LineMode,
+                "03" => BatteryMode,
+                "04" => FaultMode,
+                "05" => LineMode,
+                x if lenient => DeviceMode::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceMode,
+                        "mode",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qmod::DeviceMode;
+    use crate::error::Result;
+    use crate::pi17::codec::Codec;
+    use crate::pi17::commands::mode::{MODResponse, MOD};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_mod_payload_encode() -> Result<()> {
+        let req: <MOD as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mod_payload_decode() -> Result<()> {
+        let modes = [
+            ("00", DeviceMode::PowerOnMode),
+            ("01", DeviceMode::StandbyMode),
+            ("02", DeviceMode::LineMode),
+            ("03", DeviceMode::BatteryMode),
+            ("04", DeviceMode::FaultMode),
+            ("05", DeviceMode::LineMode),
+        ];
+
+        for (res, mode) in modes.iter() {
+            let buf = BytesMut::from(*res);
+            let item = <MOD as Command>::Response::decode(&buf)?;
+            assert_eq!(item, MODResponse { mode: mode.clone() });
+        }
+
+        let buf = BytesMut::from("06");
+        assert!(<MOD as Command>::Response::decode(&buf).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mod_command_encode() -> Result<()> {
+        let mut codec = Codec::<MOD>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P006MOD\xdd\xbe\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct PI;
+
+impl Command for PI {
+    const PROTOCOL_ID: &'static [u8] = b"PI";
+    const COMMAND_NAME: &'static str = "PI17QueryProtocolId";
+
+    type Request = ();
+    type Response = PIResponse;
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct PIResponse {
+    pub protocol_id: u64,
+}
+
+impl Response for PIResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 1..)?;
+
+        Ok(Self {
+            protocol_id: fields.parse("protocol_id")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::Result;
+    use crate::pi17::commands::pi::{PIResponse, PI};
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_pi_payload_encode() -> Result<()> {
+        let req: <PI as Command>::Request = ();
+
+        assert_eq!(req.encode()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pi_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("17");
+        let item = <PI as Command>::Response::decode(&buf)?;
+
+        assert_eq!(item, PIResponse { protocol_id: 17 });
+
+        Ok(())
+    }
+}
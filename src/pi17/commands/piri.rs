@@ -0,0 +1,139 @@
+use crate::command::{Command, Response};
+use crate::commands::qpiri::MachineType::{GridTie, Hybrid, OffGrid};
+use crate::commands::qpiri::Topology::{Transformer, Transformerless};
+use crate::commands::qpiri::{MachineType, Topology};
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use serde_derive::Serialize;
+
+pub struct PIRI;
+
+impl Command for PIRI {
+    const PROTOCOL_ID: &'static [u8] = b"PIRI";
+    const COMMAND_NAME: &'static str = "PI17QueryRatedInformation";
+
+    type Request = ();
+    type Response = PIRIResponse;
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PIRIResponse {
+    pub grid_rating_voltage: f32,
+    pub grid_rating_frequency: f32,
+    pub grid_rating_current: f32,
+    pub ac_out_rating_voltage: f32,
+    pub ac_out_rating_current: f32,
+    pub mppt_rating_current: f32,
+    pub battery_rating_voltage: f32,
+    pub mppt_tracker_count: u32,
+    pub machine_type: MachineType,
+    pub topology: Topology,
+}
+
+impl Response for PIRIResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl PIRIResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 10..)?;
+
+        Ok(Self {
+            grid_rating_voltage: fields.scaled("grid_rating_voltage", 10.0)?,
+            grid_rating_frequency: fields.scaled("grid_rating_frequency", 10.0)?,
+            grid_rating_current: fields.scaled("grid_rating_current", 10.0)?,
+            ac_out_rating_voltage: fields.scaled("ac_out_rating_voltage", 10.0)?,
+            ac_out_rating_current: fields.scaled("ac_out_rating_current", 10.0)?,
+            mppt_rating_current: fields.scaled("mppt_rating_current", 10.0)?,
+            battery_rating_voltage: fields.scaled("battery_rating_voltage", 10.0)?,
+            mppt_tracker_count: fields.parse::<u32>("mppt_tracker_count")?,
+            machine_type: match fields.str("machine_type")? {
+                "00" => GridTie,
+                "01" => OffGrid,
+                "10" => Hybrid,
+                x if lenient => MachineType::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceMachineType,
+                        "machine_type",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            topology: match fields.str("topology")? {
+                "0" => Transformerless,
+                "1" => Transformer,
+                x if lenient => Topology::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceTopology,
+                        "topology",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qpiri::{MachineType, Topology};
+    use crate::error::Result;
+    use crate::pi17::codec::Codec;
+    use crate::pi17::commands::piri::{PIRIResponse, PIRI};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_piri_payload_encode() -> Result<()> {
+        let req: <PIRI as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_piri_payload_decode_custom() -> Result<()> {
+        let res = "2300,500,0130,2300,0217,0180,480,2,10,0,1";
+
+        let buf = BytesMut::from(res);
+        let item = <PIRI as Command>::Response::decode(&buf)?;
+        assert_eq!(
+            item,
+            PIRIResponse {
+                grid_rating_voltage: 230.0,
+                grid_rating_frequency: 50.0,
+                grid_rating_current: 13.0,
+                ac_out_rating_voltage: 230.0,
+                ac_out_rating_current: 21.7,
+                mppt_rating_current: 18.0,
+                battery_rating_voltage: 48.0,
+                mppt_tracker_count: 2,
+                machine_type: MachineType::Hybrid,
+                topology: Topology::Transformerless,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_piri_command_encode() -> Result<()> {
+        let mut codec = Codec::<PIRI>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P007PIRI\xee\x38\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct WS;
+
+impl Command for WS {
+    const PROTOCOL_ID: &'static [u8] = b"WS";
+    const COMMAND_NAME: &'static str = "PI17QueryWarningStatus";
+
+    type Request = ();
+    type Response = WSResponse;
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct WSResponse {
+    pub pv1_input_loss: bool,
+    pub pv2_input_loss: bool,
+    pub pv1_voltage_high: bool,
+    pub pv2_voltage_high: bool,
+    pub battery_under: bool,
+    pub battery_low: bool,
+    pub battery_open: bool,
+    pub battery_voltage_high: bool,
+    pub battery_low_in_hybrid_mode: bool,
+    pub grid_voltage_high_loss: bool,
+    pub grid_voltage_low_loss: bool,
+    pub grid_frequency_high_loss: bool,
+    pub grid_frequency_low_loss: bool,
+    pub grid_average_voltage_over: bool,
+    pub grid_voltage_loss: bool,
+    pub grid_frequency_loss: bool,
+    pub grid_island: bool,
+    pub grid_phase_dislocation: bool,
+    pub over_temperature: bool,
+    pub over_load: bool,
+    pub emergency_power_off: bool,
+    pub grid_wave_loss: bool,
+}
+
+impl Response for WSResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 22..)?;
+
+        Ok(Self {
+            pv1_input_loss: fields.warning("pv1_input_loss")?,
+            pv2_input_loss: fields.warning("pv2_input_loss")?,
+            pv1_voltage_high: fields.warning("pv1_voltage_high")?,
+            pv2_voltage_high: fields.warning("pv2_voltage_high")?,
+            battery_under: fields.warning("battery_under")?,
+            battery_low: fields.warning("battery_low")?,
+            battery_open: fields.warning("battery_open")?,
+            battery_voltage_high: fields.warning("battery_voltage_high")?,
+            battery_low_in_hybrid_mode: fields.warning("battery_low_in_hybrid_mode")?,
+            grid_voltage_high_loss: fields.warning("grid_voltage_high_loss")?,
+            grid_voltage_low_loss: fields.warning("grid_voltage_low_loss")?,
+            grid_frequency_high_loss: fields.warning("grid_frequency_high_loss")?,
+            grid_frequency_low_loss: fields.warning("grid_frequency_low_loss")?,
+            grid_average_voltage_over: fields.warning("grid_average_voltage_over")?,
+            grid_voltage_loss: fields.warning("grid_voltage_loss")?,
+            grid_frequency_loss: fields.warning("grid_frequency_loss")?,
+            grid_island: fields.warning("grid_island")?,
+            grid_phase_dislocation: fields.warning("grid_phase_dislocation")?,
+            over_temperature: fields.warning("over_temperature")?,
+            over_load: fields.warning("over_load")?,
+            emergency_power_off: fields.warning("emergency_power_off")?,
+            grid_wave_loss: fields.warning("grid_wave_loss")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::Result;
+    use crate::pi17::codec::Codec;
+    use crate::pi17::commands::ws::WS;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_ws_payload_encode() -> Result<()> {
+        let req: <WS as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ws_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("0,1,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,0");
+        let item = <WS as Command>::Response::decode(&buf)?;
+
+        assert!(!item.pv1_input_loss);
+        assert!(item.pv2_input_loss);
+        assert!(item.battery_low);
+        assert!(item.over_temperature);
+        assert!(!item.over_load);
+        assert!(!item.grid_wave_loss);
+
+        let buf = BytesMut::from("0,1,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,2");
+        assert!(<WS as Command>::Response::decode(&buf).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ws_command_encode() -> Result<()> {
+        let mut codec = Codec::<WS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P005WS\x5b\x67\r");
+
+        Ok(())
+    }
+}
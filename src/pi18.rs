@@ -0,0 +1,5 @@
+// PI18 uses the same `^P`/`^S` request and `^D` response framing as PI17.
+#[cfg(feature = "std")]
+pub use crate::pi17::codec;
+
+pub mod commands;
@@ -0,0 +1,8 @@
+pub mod et;
+pub mod fws;
+pub mod gs;
+pub mod id;
+pub mod mode;
+pub mod pi;
+pub mod piri;
+pub mod vfw;
@@ -0,0 +1,42 @@
+use crate::command::Command;
+pub use crate::pi17::commands::et::ETResponse;
+
+// Same query and response as PI17 `ET`, named for PI18 in errors and capabilities.
+pub struct ET;
+
+impl Command for ET {
+    const PROTOCOL_ID: &'static [u8] = b"ET";
+    const COMMAND_NAME: &'static str = "PI18QueryTotalGeneratedEnergy";
+
+    type Request = ();
+    type Response = ETResponse;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request};
+    use crate::error::Result;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::et::ET;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_et_payload_encode() -> Result<()> {
+        let req: <ET as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_et_command_encode() -> Result<()> {
+        let mut codec = Codec::<ET>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(&buf.bytes()[..7], b"^P005ET");
+
+        Ok(())
+    }
+}
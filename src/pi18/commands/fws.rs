@@ -0,0 +1,109 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::fault::FaultCode;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct FWS;
+
+impl Command for FWS {
+    const PROTOCOL_ID: &'static [u8] = b"FWS";
+    const COMMAND_NAME: &'static str = "PI18QueryFaultAndWarningStatus";
+
+    type Request = ();
+    type Response = FWSResponse;
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FWSResponse {
+    pub fault_code: Option<FaultCode>,
+    pub line_fail: bool,
+    pub output_circuit_short: bool,
+    pub over_temperature: bool,
+    pub fan_locked: bool,
+    pub battery_voltage_high: bool,
+    pub battery_low: bool,
+    pub battery_under: bool,
+    pub over_load: bool,
+    pub eeprom_fail: bool,
+    pub power_limit: bool,
+    pub pv1_voltage_high: bool,
+    pub pv2_voltage_high: bool,
+    pub mppt1_overload_warning: bool,
+    pub mppt2_overload_warning: bool,
+    pub battery_too_low_to_charge_scc1: bool,
+    pub battery_too_low_to_charge_scc2: bool,
+}
+
+impl Response for FWSResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 17..)?;
+
+        Ok(Self {
+            fault_code: FaultCode::parse(fields.str("fault_code")?, "fault_code")?,
+            line_fail: fields.warning("line_fail")?,
+            output_circuit_short: fields.warning("output_circuit_short")?,
+            over_temperature: fields.warning("over_temperature")?,
+            fan_locked: fields.warning("fan_locked")?,
+            battery_voltage_high: fields.warning("battery_voltage_high")?,
+            battery_low: fields.warning("battery_low")?,
+            battery_under: fields.warning("battery_under")?,
+            over_load: fields.warning("over_load")?,
+            eeprom_fail: fields.warning("eeprom_fail")?,
+            power_limit: fields.warning("power_limit")?,
+            pv1_voltage_high: fields.warning("pv1_voltage_high")?,
+            pv2_voltage_high: fields.warning("pv2_voltage_high")?,
+            mppt1_overload_warning: fields.warning("mppt1_overload_warning")?,
+            mppt2_overload_warning: fields.warning("mppt2_overload_warning")?,
+            battery_too_low_to_charge_scc1: fields.warning("battery_too_low_to_charge_scc1")?,
+            battery_too_low_to_charge_scc2: fields.warning("battery_too_low_to_charge_scc2")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::Result;
+    use crate::fault::FaultCode;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::fws::FWS;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_fws_payload_encode() -> Result<()> {
+        let req: <FWS as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fws_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("02,1,0,0,0,0,1,0,0,0,0,0,0,0,0,0,1");
+        let item = <FWS as Command>::Response::decode(&buf)?;
+
+        assert_eq!(item.fault_code, Some(FaultCode::OverTemperature));
+        assert!(item.line_fail);
+        assert!(!item.over_temperature);
+        assert!(item.battery_low);
+        assert!(item.battery_too_low_to_charge_scc2);
+
+        let buf = BytesMut::from("02,1,0,0,0,0,1,0,0,0,0,0,0,0,0,0,x");
+        assert!(<FWS as Command>::Response::decode(&buf).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fws_command_encode() -> Result<()> {
+        let mut codec = Codec::<FWS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P006FWS\xc5\x43\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use crate::command::{Command, Response};
+use crate::error::{Error, ErrorKind, Result};
+use crate::pi18::commands::gs::BatteryPowerDirection::{Charging, Discharging};
+use crate::pi18::commands::gs::ConverterPowerDirection::{AcToDc, DcToAc};
+use crate::pi18::commands::gs::LinePowerDirection::{Input, Output};
+use crate::pi18::commands::gs::MpptChargerStatus::{Abnormal, NotCharging};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use serde_derive::Serialize;
+
+pub struct GS;
+
+impl Command for GS {
+    const PROTOCOL_ID: &'static [u8] = b"GS";
+    const COMMAND_NAME: &'static str = "PI18QueryGeneralStatus";
+
+    type Request = ();
+    type Response = GSResponse;
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GSResponse {
+    pub grid_voltage: f32,
+    pub grid_frequency: f32,
+    pub ac_out_voltage: f32,
+    pub ac_out_frequency: f32,
+    pub ac_out_apparent_power: usize,
+    pub ac_out_active_power: usize,
+    pub out_load_percent: usize,
+    pub battery_voltage: f32,
+    pub battery_scc_voltage: f32,
+    pub battery_scc2_voltage: f32,
+    pub battery_discharge_current: usize,
+    pub battery_charge_current: usize,
+    pub battery_capacity: usize,
+    pub inverter_heat_sink_temp: i32,
+    pub mppt1_charger_temp: i32,
+    pub mppt2_charger_temp: i32,
+    pub pv1_input_power: usize,
+    pub pv2_input_power: usize,
+    pub pv1_input_voltage: f32,
+    pub pv2_input_voltage: f32,
+    pub setting_changed: bool,
+    pub mppt1_charger_status: MpptChargerStatus,
+    pub mppt2_charger_status: MpptChargerStatus,
+    pub load_connected: bool,
+    pub battery_power_direction: BatteryPowerDirection,
+    pub converter_power_direction: ConverterPowerDirection,
+    pub line_power_direction: LinePowerDirection,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum MpptChargerStatus {
+    Abnormal,
+    NotCharging,
+    Charging,
+    Unknown(String),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum BatteryPowerDirection {
+    Idle,
+    Charging,
+    Discharging,
+    Unknown(String),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ConverterPowerDirection {
+    Idle,
+    AcToDc,
+    DcToAc,
+    Unknown(String),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum LinePowerDirection {
+    Idle,
+    Input,
+    Output,
+    Unknown(String),
+}
+
+impl GSResponse {
+    fn decode_charger_status(
+        src: &str,
+        field: &'static str,
+        lenient: bool,
+    ) -> Result<MpptChargerStatus> {
+        Ok(match src {
+            "0" => Abnormal,
+            "1" => NotCharging,
+            "2" => MpptChargerStatus::Charging,
+            x if lenient => MpptChargerStatus::Unknown(x.to_owned()),
+            x => {
+                return Err(Error::invalid_field(
+                    ErrorKind::InvalidDeviceStatus,
+                    field,
+                    x.as_bytes(),
+                ))
+            }
+        })
+    }
+}
+
+impl Response for GSResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl GSResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 27..)?;
+
+        Ok(Self {
+            grid_voltage: fields.scaled("grid_voltage", 10.0)?,
+            grid_frequency: fields.scaled("grid_frequency", 10.0)?,
+            ac_out_voltage: fields.scaled("ac_out_voltage", 10.0)?,
+            ac_out_frequency: fields.scaled("ac_out_frequency", 10.0)?,
+            ac_out_apparent_power: fields.parse::<usize>("ac_out_apparent_power")?,
+            ac_out_active_power: fields.parse::<usize>("ac_out_active_power")?,
+            out_load_percent: fields.parse::<usize>("out_load_percent")?,
+            battery_voltage: fields.scaled("battery_voltage", 10.0)?,
+            battery_scc_voltage: fields.scaled("battery_scc_voltage", 10.0)?,
+            battery_scc2_voltage: fields.scaled("battery_scc2_voltage", 10.0)?,
+            battery_discharge_current: fields.parse::<usize>("battery_discharge_current")?,
+            battery_charge_current: fields.parse::<usize>("battery_charge_current")?,
+            battery_capacity: fields.parse::<usize>("battery_capacity")?,
+            inverter_heat_sink_temp: fields.parse::<i32>("inverter_heat_sink_temp")?,
+            mppt1_charger_temp: fields.parse::<i32>("mppt1_charger_temp")?,
+            mppt2_charger_temp: fields.parse::<i32>("mppt2_charger_temp")?,
+            pv1_input_power: fields.parse::<usize>("pv1_input_power")?,
+            pv2_input_power: fields.parse::<usize>("pv2_input_power")?,
+            pv1_input_voltage: fields.scaled("pv1_input_voltage", 10.0)?,
+            pv2_input_voltage: fields.scaled("pv2_input_voltage", 10.0)?,
+            setting_changed: fields.flag("setting_changed")?,
+            mppt1_charger_status: Self::decode_charger_status(
+                fields.str("mppt1_charger_status")?,
+                "mppt1_charger_status",
+                lenient,
+            )?,
+            mppt2_charger_status: Self::decode_charger_status(
+                fields.str("mppt2_charger_status")?,
+                "mppt2_charger_status",
+                lenient,
+            )?,
+            load_connected: fields.flag("load_connected")?,
+            battery_power_direction: match fields.str("battery_power_direction")? {
+                "0" => BatteryPowerDirection::Idle,
+                "1" => Charging,
+                "2" => Discharging,
+                x if lenient => BatteryPowerDirection::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceStatus,
+                        "battery_power_direction",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            converter_power_direction: match fields.str("converter_power_direction")? {
+                "0" => ConverterPowerDirection::Idle,
+                "1" => AcToDc,
+                "2" => DcToAc,
+                x if lenient => ConverterPowerDirection::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceStatus,
+                        "converter_power_direction",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            line_power_direction: match fields.str("line_power_direction")? {
+                "0" => LinePowerDirection::Idle,
+                "1" => Input,
+                "2" => Output,
+                x if lenient => LinePowerDirection::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceStatus,
+                        "line_power_direction",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::{ErrorKind, Result};
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::gs::{
+        BatteryPowerDirection, ConverterPowerDirection, GSResponse, LinePowerDirection,
+        MpptChargerStatus, GS,
+    };
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_gs_payload_encode() -> Result<()> {
+        let req: <GS as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gs_payload_decode_custom() -> Result<()> {
+        let res = "2366,499,2299,500,0482,0404,008,536,000,000,000,012,100,044,038,000,1080,0000,2475,0000,0,2,0,1,1,2,1,0";
+
+        let buf = BytesMut::from(res);
+        let item = <GS as Command>::Response::decode(&buf)?;
+        assert_eq!(
+            item,
+            GSResponse {
+                grid_voltage: 236.6,
+                grid_frequency: 49.9,
+                ac_out_voltage: 229.9,
+                ac_out_frequency: 50.0,
+                ac_out_apparent_power: 482,
+                ac_out_active_power: 404,
+                out_load_percent: 8,
+                battery_voltage: 53.6,
+                battery_scc_voltage: 0.0,
+                battery_scc2_voltage: 0.0,
+                battery_discharge_current: 0,
+                battery_charge_current: 12,
+                battery_capacity: 100,
+                inverter_heat_sink_temp: 44,
+                mppt1_charger_temp: 38,
+                mppt2_charger_temp: 0,
+                pv1_input_power: 1080,
+                pv2_input_power: 0,
+                pv1_input_voltage: 247.5,
+                pv2_input_voltage: 0.0,
+                setting_changed: false,
+                mppt1_charger_status: MpptChargerStatus::Charging,
+                mppt2_charger_status: MpptChargerStatus::Abnormal,
+                load_connected: true,
+                battery_power_direction: BatteryPowerDirection::Charging,
+                converter_power_direction: ConverterPowerDirection::DcToAc,
+                line_power_direction: LinePowerDirection::Input,
+            }
+        );
+
+        // Flags are either 0 or 1.
+        let res = res.replace(",0,2,0,1,1,2,1,0", ",0,2,0,7,1,2,1,0");
+        let err = <GS as Command>::Response::decode(res.as_bytes()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPayload));
+        assert_eq!(err.field(), Some("load_connected"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gs_command_encode() -> Result<()> {
+        let mut codec = Codec::<GS>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P005GS\x58\x14\r");
+
+        Ok(())
+    }
+}
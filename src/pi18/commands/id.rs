@@ -0,0 +1,94 @@
+use crate::command::{Command, Response};
+use crate::error::{Error, ErrorKind, Result};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::str::{from_utf8, FromStr};
+use serde_derive::Serialize;
+
+pub struct ID;
+
+impl Command for ID {
+    const PROTOCOL_ID: &'static [u8] = b"ID";
+    const COMMAND_NAME: &'static str = "PI18QuerySerialNumber";
+
+    type Request = ();
+    type Response = IDResponse;
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct IDResponse {
+    pub serial_number: String,
+}
+
+impl Response for IDResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        // The first two digits are the length of the serial number, the rest is padding.
+        if src.len() < 2 {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "serial_number",
+                src,
+            ));
+        }
+
+        let len = usize::from_str(from_utf8(&src[..2])?)?;
+        if src.len() < 2 + len {
+            return Err(Error::invalid_field(
+                ErrorKind::InvalidPayload,
+                "serial_number",
+                src,
+            ));
+        }
+
+        Ok(Self {
+            serial_number: from_utf8(&src[2..2 + len])?.to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::Result;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::id::{IDResponse, ID};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_id_payload_encode() -> Result<()> {
+        let req: <ID as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_id_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("1496161801100025000000");
+        let item = <ID as Command>::Response::decode(&buf)?;
+
+        assert_eq!(
+            item,
+            IDResponse {
+                serial_number: String::from("96161801100025")
+            }
+        );
+
+        let buf = BytesMut::from("2096161801100025");
+        assert!(<ID as Command>::Response::decode(&buf).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_id_command_encode() -> Result<()> {
+        let mut codec = Codec::<ID>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P005ID\x19\xcd\r");
+
+        Ok(())
+    }
+}
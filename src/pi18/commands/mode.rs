@@ -0,0 +1,42 @@
+use crate::command::Command;
+pub use crate::pi17::commands::mode::MODResponse;
+
+// Same query and response as PI17 `MOD`, named for PI18 in errors and capabilities.
+pub struct MOD;
+
+impl Command for MOD {
+    const PROTOCOL_ID: &'static [u8] = b"MOD";
+    const COMMAND_NAME: &'static str = "PI18QueryWorkingMode";
+
+    type Request = ();
+    type Response = MODResponse;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request};
+    use crate::error::Result;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::mode::MOD;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_mod_payload_encode() -> Result<()> {
+        let req: <MOD as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mod_command_encode() -> Result<()> {
+        let mut codec = Codec::<MOD>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(&buf.bytes()[..8], b"^P006MOD");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::command::Command;
+pub use crate::pi17::commands::pi::PIResponse;
+
+// Same query and response as PI17 `PI`, named for PI18 in errors and capabilities.
+pub struct PI;
+
+impl Command for PI {
+    const PROTOCOL_ID: &'static [u8] = b"PI";
+    const COMMAND_NAME: &'static str = "PI18QueryProtocolId";
+
+    type Request = ();
+    type Response = PIResponse;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request};
+    use crate::error::Result;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::pi::PI;
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_pi_payload_encode() -> Result<()> {
+        let req: <PI as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pi_command_encode() -> Result<()> {
+        let mut codec = Codec::<PI>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(&buf.bytes()[..7], b"^P005PI");
+
+        Ok(())
+    }
+}
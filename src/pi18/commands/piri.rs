@@ -0,0 +1,250 @@
+use crate::command::{Command, Response};
+use crate::commands::qpiri::ChargeSourcePriority::{OnlySolar, SolarAndGrid, SolarFirst};
+use crate::commands::qpiri::InputVoltageRange::{Appliance, UPS};
+use crate::commands::qpiri::MachineType::{GridTie, OffGrid};
+use crate::commands::qpiri::OutputMode::{
+    ParallelOutput, Phase1Of3Output, Phase2Of3Output, Phase3Of3Output, SingleMachineOutput,
+};
+use crate::commands::qpiri::OutputSourcePriority::{
+    SBUFirst, SolarFirst as OutputSourceSolarFirst,
+};
+use crate::commands::qpiri::Topology::{Transformer, Transformerless};
+use crate::commands::qpiri::{
+    BatteryType, ChargeSourcePriority, InputVoltageRange, MachineType, OutputMode,
+    OutputSourcePriority, Topology,
+};
+use crate::error::{Error, ErrorKind, Result};
+use crate::tokenizer::Tokenizer;
+use alloc::borrow::ToOwned;
+use serde_derive::Serialize;
+
+pub struct PIRI;
+
+impl Command for PIRI {
+    const PROTOCOL_ID: &'static [u8] = b"PIRI";
+    const COMMAND_NAME: &'static str = "PI18QueryRatedInformation";
+
+    type Request = ();
+    type Response = PIRIResponse;
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PIRIResponse {
+    pub grid_rating_voltage: f32,
+    pub grid_rating_current: f32,
+    pub ac_output_rating_voltage: f32,
+    pub ac_out_rating_frequency: f32,
+    pub ac_out_rating_current: f32,
+    pub ac_out_rating_apparent_power: i32,
+    pub ac_out_rating_active_power: i32,
+    pub battery_rating_voltage: f32,
+    pub battery_recharge_voltage: f32,
+    pub battery_redischarge_voltage: f32,
+    pub battery_under_voltage: f32,
+    pub battery_bulk_voltage: f32,
+    pub battery_float_voltage: f32,
+    pub battery_type: BatteryType,
+    pub max_ac_charging_current: i32,
+    pub max_charging_current: i32,
+    pub input_voltage_range: InputVoltageRange,
+    pub output_source_priority: OutputSourcePriority,
+    pub charge_source_priority: ChargeSourcePriority,
+    pub parallel_max_num: i32,
+    pub machine_type: MachineType,
+    pub topology: Topology,
+    pub output_mode: OutputMode,
+}
+
+impl Response for PIRIResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, false)
+    }
+
+    fn decode_lenient(src: &[u8]) -> Result<Self> {
+        Self::decode_with(src, true)
+    }
+}
+
+impl PIRIResponse {
+    fn decode_with(src: &[u8], lenient: bool) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 23..)?;
+
+        Ok(Self {
+            grid_rating_voltage: fields.scaled("grid_rating_voltage", 10.0)?,
+            grid_rating_current: fields.scaled("grid_rating_current", 10.0)?,
+            ac_output_rating_voltage: fields.scaled("ac_output_rating_voltage", 10.0)?,
+            ac_out_rating_frequency: fields.scaled("ac_out_rating_frequency", 10.0)?,
+            ac_out_rating_current: fields.scaled("ac_out_rating_current", 10.0)?,
+            ac_out_rating_apparent_power: fields.parse::<i32>("ac_out_rating_apparent_power")?,
+            ac_out_rating_active_power: fields.parse::<i32>("ac_out_rating_active_power")?,
+            battery_rating_voltage: fields.scaled("battery_rating_voltage", 10.0)?,
+            battery_recharge_voltage: fields.scaled("battery_recharge_voltage", 10.0)?,
+            battery_redischarge_voltage: fields.scaled("battery_redischarge_voltage", 10.0)?,
+            battery_under_voltage: fields.scaled("battery_under_voltage", 10.0)?,
+            battery_bulk_voltage: fields.scaled("battery_bulk_voltage", 10.0)?,
+            battery_float_voltage: fields.scaled("battery_float_voltage", 10.0)?,
+            battery_type: BatteryType::decode(fields.str("battery_type")?, lenient)?,
+            max_ac_charging_current: fields.parse::<i32>("max_ac_charging_current")?,
+            max_charging_current: fields.parse::<i32>("max_charging_current")?,
+            input_voltage_range: match fields.str("input_voltage_range")? {
+                "0" => Appliance,
+                "1" => UPS,
+                x if lenient => InputVoltageRange::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceInputVoltageRange,
+                        "input_voltage_range",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            // PI18 only knows about Solar-Utility-Battery and Solar-Battery-Utility.
+            output_source_priority: match fields.str("output_source_priority")? {
+                "0" => OutputSourceSolarFirst,
+                "1" => SBUFirst,
+                x if lenient => OutputSourcePriority::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceOutputSourcePriority,
+                        "output_source_priority",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            charge_source_priority: match fields.str("charge_source_priority")? {
+                "0" => SolarFirst,
+                "1" => SolarAndGrid,
+                "2" => OnlySolar,
+                x if lenient => ChargeSourcePriority::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceChargeSourcePriority,
+                        "charge_source_priority",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            parallel_max_num: fields.parse::<i32>("parallel_max_num")?,
+            machine_type: match fields.str("machine_type")? {
+                "00" => OffGrid,
+                "01" => GridTie,
+                x if lenient => MachineType::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceMachineType,
+                        "machine_type",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            topology: match fields.str("topology")? {
+                "0" => Transformerless,
+                "1" => Transformer,
+                x if lenient => Topology::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceTopology,
+                        "topology",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+            output_mode: match fields.str("output_mode")? {
+                "0" => SingleMachineOutput,
+                "1" => ParallelOutput,
+                "2" => Phase1Of3Output,
+                "3" => Phase2Of3Output,
+                "4" => Phase3Of3Output,
+                x if lenient => OutputMode::Unknown(x.to_owned()),
+                x => {
+                    return Err(Error::invalid_field(
+                        ErrorKind::InvalidDeviceOutputMode,
+                        "output_mode",
+                        x.as_bytes(),
+                    ))
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::commands::qpiri::{
+        BatteryType, ChargeSourcePriority, InputVoltageRange, MachineType, OutputMode,
+        OutputSourcePriority, Topology,
+    };
+    use crate::error::Result;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::piri::{PIRIResponse, PIRI};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_piri_payload_encode() -> Result<()> {
+        let req: <PIRI as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_piri_payload_decode_custom() -> Result<()> {
+        let res = "2300,217,2300,500,217,5000,5000,480,500,460,421,540,540,2,010,060,0,1,1,6,00,0,0,1,2,00";
+
+        let buf = BytesMut::from(res);
+        let item = <PIRI as Command>::Response::decode(&buf)?;
+        assert_eq!(
+            item,
+            PIRIResponse {
+                grid_rating_voltage: 230.0,
+                grid_rating_current: 21.7,
+                ac_output_rating_voltage: 230.0,
+                ac_out_rating_frequency: 50.0,
+                ac_out_rating_current: 21.7,
+                ac_out_rating_apparent_power: 5000,
+                ac_out_rating_active_power: 5000,
+                battery_rating_voltage: 48.0,
+                battery_recharge_voltage: 50.0,
+                battery_redischarge_voltage: 46.0,
+                battery_under_voltage: 42.1,
+                battery_bulk_voltage: 54.0,
+                battery_float_voltage: 54.0,
+                battery_type: BatteryType::User,
+                max_ac_charging_current: 10,
+                max_charging_current: 60,
+                input_voltage_range: InputVoltageRange::Appliance,
+                output_source_priority: OutputSourcePriority::SBUFirst,
+                charge_source_priority: ChargeSourcePriority::SolarAndGrid,
+                parallel_max_num: 6,
+                machine_type: MachineType::OffGrid,
+                topology: Topology::Transformerless,
+                output_mode: OutputMode::SingleMachineOutput,
+            }
+        );
+
+        // The lithium profiles use the PI30 codes.
+        let res = res.replace(",540,2,010,", ",540,4,010,");
+        let item = <PIRI as Command>::Response::decode(res.as_bytes())?;
+        assert_eq!(item.battery_type, BatteryType::Pylontech);
+
+        let res = res.replace(",540,4,010,", ",540,7,010,");
+        assert!(<PIRI as Command>::Response::decode(res.as_bytes()).is_err());
+        let item = <PIRI as Command>::Response::decode_lenient(res.as_bytes())?;
+        assert_eq!(item.battery_type, BatteryType::Unknown(String::from("7")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_piri_command_encode() -> Result<()> {
+        let mut codec = Codec::<PIRI>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P007PIRI\xee\x38\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::command::{Command, Response};
+use crate::error::Result;
+use crate::tokenizer::Tokenizer;
+use serde_derive::Serialize;
+
+pub struct VFW;
+
+impl Command for VFW {
+    const PROTOCOL_ID: &'static [u8] = b"VFW";
+    const COMMAND_NAME: &'static str = "PI18QueryFirmwareVersion";
+
+    type Request = ();
+    type Response = VFWResponse;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct VFWResponse {
+    pub main_cpu: u64,
+    pub slave1_cpu: u64,
+    pub slave2_cpu: u64,
+}
+
+impl Response for VFWResponse {
+    fn decode(src: &[u8]) -> Result<Self> {
+        let mut fields = Tokenizer::new(src, b',', 3..)?;
+
+        Ok(Self {
+            main_cpu: fields.parse::<u64>("main_cpu")?,
+            slave1_cpu: fields.parse::<u64>("slave1_cpu")?,
+            slave2_cpu: fields.parse::<u64>("slave2_cpu")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{Command, Request, Response};
+    use crate::error::Result;
+    use crate::pi18::codec::Codec;
+    use crate::pi18::commands::vfw::{VFWResponse, VFW};
+    use bytes::{Buf, BytesMut};
+    use tokio_util::codec::Encoder;
+
+    #[test]
+    fn test_vfw_payload_encode() -> Result<()> {
+        let req: <VFW as Command>::Request = ();
+        assert_eq!(req.encode()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vfw_payload_decode() -> Result<()> {
+        let buf = BytesMut::from("05220,00000,00000");
+        let item = <VFW as Command>::Response::decode(&buf)?;
+
+        assert_eq!(
+            item,
+            VFWResponse {
+                main_cpu: 5220,
+                slave1_cpu: 0,
+                slave2_cpu: 0,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vfw_command_encode() -> Result<()> {
+        let mut codec = Codec::<VFW>::new();
+
+        let mut buf = BytesMut::new();
+        codec.encode((), &mut buf)?;
+
+        assert_eq!(buf.bytes(), b"^P006VFW\xf6\xe6\r");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::commands::qpigs::QPIGSResponse;
+use crate::commands::qpigs2::QPIGS2Response;
+use crate::pi17::commands::gs::GSResponse;
+use crate::pi18::commands::gs::GSResponse as PI18GSResponse;
+use serde_derive::Serialize;
+
+/// A single PV input (string).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct PvInput {
+    pub voltage: f32,
+    pub current: f32,
+    pub power: f32,
+}
+
+/// The PV inputs of a unit, built by `Inverter::pv_status`.
+///
+/// `pv2` is left out for PI30 units with a single MPPT tracker, which don't answer `QPIGS2`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PvStatus {
+    pub pv1: PvInput,
+    pub pv2: Option<PvInput>,
+}
+
+impl PvStatus {
+    pub fn inputs(&self) -> impl Iterator<Item = &PvInput> {
+        core::iter::once(&self.pv1).chain(self.pv2.as_ref())
+    }
+
+    pub fn total_current(&self) -> f32 {
+        self.inputs().map(|x| x.current).sum()
+    }
+
+    pub fn total_power(&self) -> f32 {
+        self.inputs().map(|x| x.power).sum()
+    }
+}
+
+// Older firmware doesn't report the charging power, so it is estimated from the whole amps sent.
+impl From<&QPIGSResponse> for PvInput {
+    fn from(gs: &QPIGSResponse) -> Self {
+        let current = gs.pv_input_current as f32;
+
+        Self {
+            voltage: gs.pv_input_voltage,
+            current,
+            power: gs
+                .pv_charging_power
+                .map_or(gs.pv_input_voltage * current, |x| x as f32),
+        }
+    }
+}
+
+impl From<&QPIGS2Response> for PvInput {
+    fn from(gs: &QPIGS2Response) -> Self {
+        Self {
+            voltage: gs.pv_input_voltage,
+            current: gs.pv_input_current,
+            power: gs.pv_charging_power as f32,
+        }
+    }
+}
+
+impl From<(&QPIGSResponse, Option<&QPIGS2Response>)> for PvStatus {
+    fn from((gs, gs2): (&QPIGSResponse, Option<&QPIGS2Response>)) -> Self {
+        Self {
+            pv1: PvInput::from(gs),
+            pv2: gs2.map(PvInput::from),
+        }
+    }
+}
+
+impl From<&GSResponse> for PvStatus {
+    fn from(gs: &GSResponse) -> Self {
+        let input = |voltage: f32, current: f32| PvInput {
+            voltage,
+            current,
+            power: voltage * current,
+        };
+
+        Self {
+            pv1: input(gs.pv1_input_voltage, gs.pv1_input_current),
+            pv2: Some(input(gs.pv2_input_voltage, gs.pv2_input_current)),
+        }
+    }
+}
+
+// PI18 sends power instead of current.
+impl From<&PI18GSResponse> for PvStatus {
+    fn from(gs: &PI18GSResponse) -> Self {
+        let input = |voltage: f32, power: usize| PvInput {
+            voltage,
+            current: if voltage > 0.0 {
+                power as f32 / voltage
+            } else {
+                0.0
+            },
+            power: power as f32,
+        };
+
+        Self {
+            pv1: input(gs.pv1_input_voltage, gs.pv1_input_power),
+            pv2: Some(input(gs.pv2_input_voltage, gs.pv2_input_power)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::capabilities::Capabilities;
+    use crate::error::Result;
+    use crate::inverter::Inverter;
+    use crate::mock::MockStream;
+    use crate::pv::{PvInput, PvStatus};
+
+    const QPIGS: &[u8] = b"001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0004 288.4 27.12 00005 10110110 17 04 01150 100";
+
+    #[tokio::test]
+    async fn test_pv_status_dual_mppt() -> Result<()> {
+        let stream = MockStream::new()
+            .respond(b"QPIGS", QPIGS)
+            .respond(b"QPIGS2", b"03.1 327.3 01026");
+        let mut inverter = Inverter::from_stream(stream);
+
+        let pv = inverter.pv_status().await?;
+        assert_eq!(
+            pv,
+            PvStatus {
+                pv1: PvInput {
+                    voltage: 288.4,
+                    current: 4.0,
+                    power: 1150.0,
+                },
+                pv2: Some(PvInput {
+                    voltage: 327.3,
+                    current: 3.1,
+                    power: 1026.0,
+                }),
+            }
+        );
+        assert!((pv.total_current() - 7.1).abs() < 1e-4);
+        assert_eq!(pv.total_power(), 2176.0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pv_status_single_mppt() -> Result<()> {
+        let stream = MockStream::new().respond(b"QPIGS", QPIGS);
+        let mut inverter = Inverter::from_stream(stream);
+
+        let pv = inverter.pv_status().await?;
+        assert_eq!(pv.pv2, None);
+        assert_eq!(pv.total_current(), 4.0);
+        assert_eq!(pv.total_power(), 1150.0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pv_status_pi17() -> Result<()> {
+        let stream = MockStream::new().respond(
+            b"^P005GS",
+                b"2356,0000,0042,0000,0532,080,-0125,2301,0000,0000,4998,0012,0000,0000,2299,0000,0000,5000,0031,0000,0000,035,041,000,0",
+        );
+        let mut inverter = Inverter::from_stream(stream).with_capabilities(Capabilities::new(17));
+
+        let pv = inverter.pv_status().await?;
+        assert_eq!(pv.pv1.voltage, 235.6);
+        assert_eq!(pv.pv2.map(|x| x.power), Some(0.0));
+        assert_eq!(pv.total_current(), 4.2);
+
+        Ok(())
+    }
+}
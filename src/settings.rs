@@ -0,0 +1,99 @@
+use crate::commands::qpiri::{
+    BatteryType, ChargeSourcePriority, InputVoltageRange, OutputMode, OutputSourcePriority,
+    QPIRIResponse,
+};
+use crate::pi18::commands::piri::PIRIResponse as PI18PIRIResponse;
+use serde_derive::Serialize;
+
+/// Device settings independent of the protocol the device speaks.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InverterSettings {
+    pub ac_output_voltage: f32,
+    pub ac_output_frequency: f32,
+    pub battery_rating_voltage: f32,
+    pub battery_type: BatteryType,
+    pub battery_recharge_voltage: f32,
+    pub battery_redischarge_voltage: f32,
+    pub battery_under_voltage: f32,
+    pub battery_bulk_voltage: f32,
+    pub battery_float_voltage: f32,
+    pub max_ac_charging_current: i32,
+    pub max_charging_current: i32,
+    pub input_voltage_range: InputVoltageRange,
+    pub output_source_priority: OutputSourcePriority,
+    pub charge_source_priority: ChargeSourcePriority,
+    pub output_mode: OutputMode,
+}
+
+impl From<&QPIRIResponse> for InverterSettings {
+    fn from(piri: &QPIRIResponse) -> Self {
+        Self {
+            ac_output_voltage: piri.ac_output_rating_voltage,
+            ac_output_frequency: piri.ac_out_rating_frequency,
+            battery_rating_voltage: piri.battery_rating_voltage,
+            battery_type: piri.battery_type.clone(),
+            battery_recharge_voltage: piri.battery_recharge_voltage,
+            battery_redischarge_voltage: piri.battery_redischarge_voltage,
+            battery_under_voltage: piri.battery_under_voltage,
+            battery_bulk_voltage: piri.battery_bulk_voltage,
+            battery_float_voltage: piri.battery_float_voltage,
+            max_ac_charging_current: piri.max_ac_charging_current,
+            max_charging_current: piri.max_charging_current,
+            input_voltage_range: piri.input_voltage_range.clone(),
+            output_source_priority: piri.output_source_priority.clone(),
+            charge_source_priority: piri.charge_source_priority.clone(),
+            output_mode: piri.output_mode.clone(),
+        }
+    }
+}
+
+impl From<&PI18PIRIResponse> for InverterSettings {
+    fn from(piri: &PI18PIRIResponse) -> Self {
+        Self {
+            ac_output_voltage: piri.ac_output_rating_voltage,
+            ac_output_frequency: piri.ac_out_rating_frequency,
+            battery_rating_voltage: piri.battery_rating_voltage,
+            battery_type: piri.battery_type.clone(),
+            battery_recharge_voltage: piri.battery_recharge_voltage,
+            battery_redischarge_voltage: piri.battery_redischarge_voltage,
+            battery_under_voltage: piri.battery_under_voltage,
+            battery_bulk_voltage: piri.battery_bulk_voltage,
+            battery_float_voltage: piri.battery_float_voltage,
+            max_ac_charging_current: piri.max_ac_charging_current,
+            max_charging_current: piri.max_charging_current,
+            input_voltage_range: piri.input_voltage_range.clone(),
+            output_source_priority: piri.output_source_priority.clone(),
+            charge_source_priority: piri.charge_source_priority.clone(),
+            output_mode: piri.output_mode.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::Response;
+    use crate::commands::qpiri::{BatteryType, OutputSourcePriority, QPIRIResponse};
+    use crate::error::Result;
+    use crate::settings::InverterSettings;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_settings_from_pi30() -> Result<()> {
+        let piri = QPIRIResponse::decode(&BytesMut::from(
+            "230.0 13.0 230.0 50.0 13.0 3000 2400 24.0 23.0 21.0 28.2 27.0 0 30 60 0 0 0 - 01 1 0 27.0 0 0",
+        ))?;
+
+        let settings = InverterSettings::from(&piri);
+        assert_eq!(settings.ac_output_voltage, 230.0);
+        assert_eq!(settings.battery_rating_voltage, 24.0);
+        assert_eq!(settings.battery_bulk_voltage, 28.2);
+        assert_eq!(settings.battery_type, BatteryType::AGM);
+        assert_eq!(settings.max_charging_current, 60);
+        assert_eq!(
+            settings.output_source_priority,
+            OutputSourcePriority::GridFirst
+        );
+
+        Ok(())
+    }
+}
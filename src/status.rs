@@ -0,0 +1,410 @@
+use crate::commands::qmod::{DeviceMode, QMODResponse};
+use crate::commands::qpigs::QPIGSResponse;
+use crate::commands::qpiws::QPIWSResponse;
+use crate::pi17::commands::gs::GSResponse;
+use crate::pi17::commands::mode::MODResponse;
+use crate::pi17::commands::ws::WSResponse;
+use crate::pi18::commands::fws::FWSResponse;
+use crate::pi18::commands::gs::GSResponse as PI18GSResponse;
+use alloc::vec::Vec;
+use serde_derive::Serialize;
+
+/// Device status independent of the protocol the device speaks.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InverterStatus {
+    pub mode: DeviceMode,
+    pub grid_voltage: f32,
+    pub grid_frequency: f32,
+    pub ac_out_voltage: f32,
+    pub ac_out_frequency: f32,
+    pub ac_out_apparent_power: Option<u32>,
+    pub ac_out_active_power: Option<u32>,
+    pub out_load_percent: Option<u32>,
+    pub battery_voltage: f32,
+    // Positive while charging, negative while discharging.
+    pub battery_current: f32,
+    pub battery_capacity: u32,
+    pub inverter_temperature: i32,
+    // The first PV input only, see `PvStatus` for units with two inputs and their totals.
+    pub pv_input_voltage: f32,
+    pub pv_input_current: f32,
+    pub pv_input_power: f32,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Warning {
+    InverterFault,
+    BusOver,
+    BusUnder,
+    BusSoftFail,
+    LineFail,
+    OpvShort,
+    InverterVoltageTooLow,
+    InverterVoltageTooHigh,
+    OverTemperature,
+    FanLocked,
+    BatteryVoltageHigh,
+    BatteryLowAlarm,
+    BatteryUnderShutdown,
+    OverLoad,
+    EepromFault,
+    InverterOverCurrent,
+    InverterSoftFail,
+    SelfTestFail,
+    OpDcVoltageOver,
+    BatteryOpen,
+    CurrentSensorFail,
+    BatteryShort,
+    PowerLimit,
+    PvVoltageHigh,
+    MpptOverloadFault,
+    MpptOverloadWarning,
+    BatteryTooLowToCharge,
+    PvInputLoss,
+    EmergencyPowerOff,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Severity {
+    Warning,
+    Fault,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum WarningCategory {
+    Inverter,
+    Bus,
+    Grid,
+    Output,
+    Battery,
+    Pv,
+    Temperature,
+    System,
+}
+
+impl Warning {
+    pub fn category(&self) -> WarningCategory {
+        match self {
+            Self::InverterFault | Self::InverterOverCurrent | Self::InverterSoftFail => {
+                WarningCategory::Inverter
+            }
+            Self::BusOver | Self::BusUnder | Self::BusSoftFail => WarningCategory::Bus,
+            Self::LineFail => WarningCategory::Grid,
+            Self::OpvShort
+            | Self::InverterVoltageTooLow
+            | Self::InverterVoltageTooHigh
+            | Self::OverLoad
+            | Self::OpDcVoltageOver
+            | Self::PowerLimit => WarningCategory::Output,
+            Self::BatteryVoltageHigh
+            | Self::BatteryLowAlarm
+            | Self::BatteryUnderShutdown
+            | Self::BatteryOpen
+            | Self::BatteryShort
+            | Self::BatteryTooLowToCharge => WarningCategory::Battery,
+            Self::PvVoltageHigh
+            | Self::MpptOverloadFault
+            | Self::MpptOverloadWarning
+            | Self::PvInputLoss => WarningCategory::Pv,
+            Self::OverTemperature | Self::FanLocked => WarningCategory::Temperature,
+            Self::EepromFault
+            | Self::SelfTestFail
+            | Self::CurrentSensorFail
+            | Self::EmergencyPowerOff => WarningCategory::System,
+        }
+    }
+
+    /// Severity following the PI30 warning table. Some conditions are only faults while the
+    /// inverter fault flag is also set.
+    pub fn severity(&self, inverter_fault: bool) -> Severity {
+        match self {
+            Self::InverterFault
+            | Self::BusOver
+            | Self::BusUnder
+            | Self::BusSoftFail
+            | Self::InverterVoltageTooLow
+            | Self::InverterVoltageTooHigh
+            | Self::InverterOverCurrent
+            | Self::InverterSoftFail
+            | Self::SelfTestFail
+            | Self::OpDcVoltageOver
+            | Self::BatteryOpen
+            | Self::CurrentSensorFail
+            | Self::BatteryShort => Severity::Fault,
+            Self::OverTemperature | Self::FanLocked | Self::BatteryVoltageHigh | Self::OverLoad
+                if inverter_fault =>
+            {
+                Severity::Fault
+            }
+            _ => Severity::Warning,
+        }
+    }
+}
+
+// Keep the warnings sorted and without duplicates when several device flags map to the same one.
+fn collect_warnings(flags: &[(bool, Warning)]) -> Vec<Warning> {
+    let mut warnings = flags
+        .iter()
+        .filter(|(active, _)| *active)
+        .map(|(_, warning)| *warning)
+        .collect::<Vec<_>>();
+    warnings.sort();
+    warnings.dedup();
+
+    warnings
+}
+
+// Flags in QPIWS order.
+pub(crate) fn pi30_warnings(ws: &QPIWSResponse) -> [(bool, Warning); 27] {
+    [
+        (ws.inverter_fault, Warning::InverterFault),
+        (ws.bus_over, Warning::BusOver),
+        (ws.bus_under, Warning::BusUnder),
+        (ws.bus_soft_fail, Warning::BusSoftFail),
+        (ws.line_fail, Warning::LineFail),
+        (ws.opv_short, Warning::OpvShort),
+        (ws.inverter_voltage_too_low, Warning::InverterVoltageTooLow),
+        (
+            ws.inverter_voltage_too_high,
+            Warning::InverterVoltageTooHigh,
+        ),
+        (ws.over_temperature, Warning::OverTemperature),
+        (ws.fan_locked, Warning::FanLocked),
+        (ws.battery_voltage_high, Warning::BatteryVoltageHigh),
+        (ws.battery_low_alarm, Warning::BatteryLowAlarm),
+        (ws.battery_under_shutdown, Warning::BatteryUnderShutdown),
+        (ws.over_load, Warning::OverLoad),
+        (ws.eeprom_fault, Warning::EepromFault),
+        (ws.inverter_over_current, Warning::InverterOverCurrent),
+        (ws.inverter_soft_fail, Warning::InverterSoftFail),
+        (ws.self_test_fail, Warning::SelfTestFail),
+        (ws.op_dc_voltage_over, Warning::OpDcVoltageOver),
+        (ws.bat_open, Warning::BatteryOpen),
+        (ws.current_sensor_fail, Warning::CurrentSensorFail),
+        (ws.battery_short, Warning::BatteryShort),
+        (ws.power_limit, Warning::PowerLimit),
+        (ws.pv_voltage_high, Warning::PvVoltageHigh),
+        (ws.mppt_overload_fault, Warning::MpptOverloadFault),
+        (ws.mppt_overload_warning, Warning::MpptOverloadWarning),
+        (ws.battery_too_low_to_charge, Warning::BatteryTooLowToCharge),
+    ]
+}
+
+impl From<(&QPIGSResponse, &QMODResponse, &QPIWSResponse)> for InverterStatus {
+    fn from((gs, mode, ws): (&QPIGSResponse, &QMODResponse, &QPIWSResponse)) -> Self {
+        Self {
+            warnings: collect_warnings(&pi30_warnings(ws)),
+            ..Self::from((gs, mode))
+        }
+    }
+}
+
+// Backends without a warning status query, such as Modbus, report no warnings.
+impl From<(&QPIGSResponse, &QMODResponse)> for InverterStatus {
+    fn from((gs, mode): (&QPIGSResponse, &QMODResponse)) -> Self {
+        Self {
+            mode: mode.mode.clone(),
+            grid_voltage: gs.grid_voltage,
+            grid_frequency: gs.grid_frequency,
+            ac_out_voltage: gs.ac_out_voltage,
+            ac_out_frequency: gs.ac_out_frequency,
+            ac_out_apparent_power: Some(gs.ac_out_apparent_power as u32),
+            ac_out_active_power: Some(gs.ac_out_active_power as u32),
+            out_load_percent: Some(gs.out_load_percent as u32),
+            battery_voltage: gs.battery_voltage,
+            battery_current: gs.battery_charge_current as f32 - gs.battery_discharge_current as f32,
+            battery_capacity: gs.battery_capacity as u32,
+            inverter_temperature: gs.inverter_heat_sink_temp as i32,
+            pv_input_voltage: gs.pv_input_voltage,
+            pv_input_current: gs.pv_input_current as f32,
+            pv_input_power: gs.pv_input_voltage * gs.pv_input_current as f32,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl From<(&GSResponse, &MODResponse, &WSResponse)> for InverterStatus {
+    fn from((gs, mode, ws): (&GSResponse, &MODResponse, &WSResponse)) -> Self {
+        Self {
+            mode: mode.mode.clone(),
+            grid_voltage: gs.grid_voltage[0],
+            grid_frequency: gs.grid_frequency,
+            ac_out_voltage: gs.ac_out_voltage[0],
+            ac_out_frequency: gs.ac_out_frequency,
+            ac_out_apparent_power: None,
+            ac_out_active_power: None,
+            out_load_percent: None,
+            battery_voltage: gs.battery_voltage,
+            battery_current: gs.battery_current,
+            battery_capacity: gs.battery_capacity,
+            inverter_temperature: gs.inner_temperature,
+            pv_input_voltage: gs.pv1_input_voltage,
+            pv_input_current: gs.pv1_input_current,
+            pv_input_power: gs.pv1_input_voltage * gs.pv1_input_current,
+            warnings: collect_warnings(&[
+                (ws.pv1_input_loss, Warning::PvInputLoss),
+                (ws.pv2_input_loss, Warning::PvInputLoss),
+                (ws.pv1_voltage_high, Warning::PvVoltageHigh),
+                (ws.pv2_voltage_high, Warning::PvVoltageHigh),
+                (ws.battery_under, Warning::BatteryUnderShutdown),
+                (ws.battery_low, Warning::BatteryLowAlarm),
+                (ws.battery_open, Warning::BatteryOpen),
+                (ws.battery_voltage_high, Warning::BatteryVoltageHigh),
+                (ws.battery_low_in_hybrid_mode, Warning::BatteryLowAlarm),
+                (ws.grid_voltage_high_loss, Warning::LineFail),
+                (ws.grid_voltage_low_loss, Warning::LineFail),
+                (ws.grid_frequency_high_loss, Warning::LineFail),
+                (ws.grid_frequency_low_loss, Warning::LineFail),
+                (ws.grid_average_voltage_over, Warning::LineFail),
+                (ws.grid_voltage_loss, Warning::LineFail),
+                (ws.grid_frequency_loss, Warning::LineFail),
+                (ws.grid_island, Warning::LineFail),
+                (ws.grid_phase_dislocation, Warning::LineFail),
+                (ws.over_temperature, Warning::OverTemperature),
+                (ws.over_load, Warning::OverLoad),
+                (ws.emergency_power_off, Warning::EmergencyPowerOff),
+                (ws.grid_wave_loss, Warning::LineFail),
+            ]),
+        }
+    }
+}
+
+impl From<(&PI18GSResponse, &MODResponse, &FWSResponse)> for InverterStatus {
+    fn from((gs, mode, fws): (&PI18GSResponse, &MODResponse, &FWSResponse)) -> Self {
+        Self {
+            mode: mode.mode.clone(),
+            grid_voltage: gs.grid_voltage,
+            grid_frequency: gs.grid_frequency,
+            ac_out_voltage: gs.ac_out_voltage,
+            ac_out_frequency: gs.ac_out_frequency,
+            ac_out_apparent_power: Some(gs.ac_out_apparent_power as u32),
+            ac_out_active_power: Some(gs.ac_out_active_power as u32),
+            out_load_percent: Some(gs.out_load_percent as u32),
+            battery_voltage: gs.battery_voltage,
+            battery_current: gs.battery_charge_current as f32 - gs.battery_discharge_current as f32,
+            battery_capacity: gs.battery_capacity as u32,
+            inverter_temperature: gs.inverter_heat_sink_temp,
+            pv_input_voltage: gs.pv1_input_voltage,
+            pv_input_current: if gs.pv1_input_voltage > 0.0 {
+                gs.pv1_input_power as f32 / gs.pv1_input_voltage
+            } else {
+                0.0
+            },
+            pv_input_power: gs.pv1_input_power as f32,
+            warnings: collect_warnings(&[
+                (fws.fault_code.is_some(), Warning::InverterFault),
+                (fws.line_fail, Warning::LineFail),
+                (fws.output_circuit_short, Warning::OpvShort),
+                (fws.over_temperature, Warning::OverTemperature),
+                (fws.fan_locked, Warning::FanLocked),
+                (fws.battery_voltage_high, Warning::BatteryVoltageHigh),
+                (fws.battery_low, Warning::BatteryLowAlarm),
+                (fws.battery_under, Warning::BatteryUnderShutdown),
+                (fws.over_load, Warning::OverLoad),
+                (fws.eeprom_fail, Warning::EepromFault),
+                (fws.power_limit, Warning::PowerLimit),
+                (fws.pv1_voltage_high, Warning::PvVoltageHigh),
+                (fws.pv2_voltage_high, Warning::PvVoltageHigh),
+                (fws.mppt1_overload_warning, Warning::MpptOverloadWarning),
+                (fws.mppt2_overload_warning, Warning::MpptOverloadWarning),
+                (
+                    fws.battery_too_low_to_charge_scc1,
+                    Warning::BatteryTooLowToCharge,
+                ),
+                (
+                    fws.battery_too_low_to_charge_scc2,
+                    Warning::BatteryTooLowToCharge,
+                ),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::Response;
+    use crate::commands::qmod::{DeviceMode, QMODResponse};
+    use crate::commands::qpigs::QPIGSResponse;
+    use crate::commands::qpiws::QPIWSResponse;
+    use crate::error::Result;
+    use crate::pi17::commands::gs::GSResponse;
+    use crate::pi17::commands::mode::MODResponse;
+    use crate::pi17::commands::ws::WSResponse;
+    use crate::pi18::commands::fws::FWSResponse;
+    use crate::pi18::commands::gs::GSResponse as PI18GSResponse;
+    use crate::status::{InverterStatus, Warning};
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_status_from_pi30() -> Result<()> {
+        let gs = QPIGSResponse::decode(&BytesMut::from(
+            "001.0 00.0 229.0 50.0 0091 0091 003 420 27.16 000 100 0336 0002 074.9 27.12 00005 10110110 17 04 00010 100",
+        ))?;
+        let mode = QMODResponse::decode(&BytesMut::from("B"))?;
+        let ws = QPIWSResponse::decode(&BytesMut::from("00000100000010000000000000000000"))?;
+
+        let status = InverterStatus::from((&gs, &mode, &ws));
+        assert_eq!(status.mode, DeviceMode::BatteryMode);
+        assert_eq!(status.ac_out_voltage, 229.0);
+        assert_eq!(status.ac_out_active_power, Some(91));
+        assert_eq!(status.battery_current, -5.0);
+        assert_eq!(status.inverter_temperature, 336);
+        assert_eq!(status.pv_input_power, 74.9 * 2.0);
+        assert_eq!(
+            status.warnings,
+            vec![Warning::LineFail, Warning::BatteryLowAlarm]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_status_from_pi17() -> Result<()> {
+        let gs = GSResponse::decode(&BytesMut::from(
+            "2000,1000,0050,0020,0532,080,-0125,2301,0000,0000,4998,0012,0000,0000,2299,0000,0000,5000,0031,0000,0000,035,041,000,0",
+        ))?;
+        let mode = MODResponse::decode(&BytesMut::from("05"))?;
+        let ws = WSResponse::decode(&BytesMut::from(
+            "0,1,0,0,0,1,0,0,1,0,0,0,0,0,1,1,0,0,0,0,0,0",
+        ))?;
+
+        let status = InverterStatus::from((&gs, &mode, &ws));
+        assert_eq!(status.mode, DeviceMode::LineMode);
+        assert_eq!(status.grid_voltage, 230.1);
+        assert_eq!(status.battery_current, -12.5);
+        assert_eq!(status.pv_input_voltage, 200.0);
+        assert_eq!(status.pv_input_current, 5.0);
+        assert_eq!(status.pv_input_power, 1000.0);
+        assert_eq!(
+            status.warnings,
+            vec![
+                Warning::LineFail,
+                Warning::BatteryLowAlarm,
+                Warning::PvInputLoss
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_status_from_pi18() -> Result<()> {
+        let gs = PI18GSResponse::decode(&BytesMut::from(
+            "2366,499,2299,500,0482,0404,008,536,000,000,000,012,100,044,038,000,1000,0500,2500,1000,0,2,2,1,1,2,1,0",
+        ))?;
+        let mode = MODResponse::decode(&BytesMut::from("02"))?;
+        let fws = FWSResponse::decode(&BytesMut::from("00,0,0,0,0,0,0,0,1,0,0,0,0,0,0,0,0"))?;
+
+        let status = InverterStatus::from((&gs, &mode, &fws));
+        assert_eq!(status.mode, DeviceMode::LineMode);
+        assert_eq!(status.ac_out_active_power, Some(404));
+        assert_eq!(status.battery_current, 12.0);
+        assert_eq!(status.pv_input_voltage, 250.0);
+        assert_eq!(status.pv_input_current, 4.0);
+        assert_eq!(status.pv_input_power, 1000.0);
+        assert_eq!(status.warnings, vec![Warning::OverLoad]);
+
+        Ok(())
+    }
+}
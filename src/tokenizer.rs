@@ -0,0 +1,317 @@
+use crate::error::{Error, ErrorKind, Result};
+use core::convert::TryFrom;
+use core::ops::{Bound, RangeBounds};
+use core::str::{from_utf8, FromStr};
+
+/// Walks the separator-delimited fields of a response payload, parsing each one in place.
+///
+/// The number of fields is checked up front, so decoders can read the fields they know about
+/// without bounds checks of their own.
+pub(crate) struct Tokenizer<'a> {
+    src: &'a [u8],
+    separator: u8,
+    index: usize,
+    count: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub(crate) fn new(
+        src: &'a [u8],
+        separator: u8,
+        expected: impl RangeBounds<usize>,
+    ) -> Result<Self> {
+        let count = src.split(|&x| x == separator).count();
+        check_field_count(count, expected)?;
+
+        Ok(Self {
+            src,
+            separator,
+            index: 0,
+            count,
+        })
+    }
+
+    /// The next field as sent.
+    pub(crate) fn raw(&mut self, field: &'static str) -> Result<&'a [u8]> {
+        if self.index == self.count {
+            return Err(Error::invalid_field(
+                ErrorKind::TooFewFields {
+                    expected: self.index + 1,
+                    actual: self.count,
+                },
+                field,
+                b"",
+            ));
+        }
+        self.index += 1;
+
+        Ok(match self.src.iter().position(|&x| x == self.separator) {
+            Some(index) => {
+                let (item, rest) = self.src.split_at(index);
+                self.src = &rest[1..];
+                item
+            }
+            None => self.src,
+        })
+    }
+
+    pub(crate) fn str(&mut self, field: &'static str) -> Result<&'a str> {
+        let src = self.raw(field)?;
+        from_utf8(src).map_err(|e| Error::from(e).with_field(field, src))
+    }
+
+    pub(crate) fn parse<T: FromField>(&mut self, field: &'static str) -> Result<T> {
+        let src = self.raw(field)?;
+        T::from_field(src).map_err(|e| e.with_field(field, src))
+    }
+
+    /// An integer field sent in `1 / divisor` units.
+    pub(crate) fn scaled(&mut self, field: &'static str, divisor: f32) -> Result<f32> {
+        Ok(self.parse::<i32>(field)? as f32 / divisor)
+    }
+
+    /// A `0` / `1` warning flag.
+    pub(crate) fn warning(&mut self, field: &'static str) -> Result<bool> {
+        match self.raw(field)? {
+            b"0" => Ok(false),
+            b"1" => Ok(true),
+            x => Err(Error::invalid_field(
+                ErrorKind::InvalidWarningStatus,
+                field,
+                x,
+            )),
+        }
+    }
+
+    /// A `0` / `1` setting or state.
+    pub(crate) fn flag(&mut self, field: &'static str) -> Result<bool> {
+        match self.raw(field)? {
+            b"0" => Ok(false),
+            b"1" => Ok(true),
+            x => Err(Error::invalid_field(ErrorKind::InvalidPayload, field, x)),
+        }
+    }
+
+    pub(crate) fn skip(&mut self, field: &'static str) -> Result<()> {
+        self.raw(field).map(|_| ())
+    }
+
+    /// The number of fields not read yet, for trailing fields older firmware leaves out.
+    pub(crate) fn remaining(&self) -> usize {
+        self.count - self.index
+    }
+}
+
+pub(crate) fn check_field_count(actual: usize, expected: impl RangeBounds<usize>) -> Result<()> {
+    match expected.start_bound() {
+        Bound::Included(&x) if actual < x => {
+            return Err(Error::new(ErrorKind::TooFewFields {
+                expected: x,
+                actual,
+            }))
+        }
+        _ => {}
+    }
+
+    match expected.end_bound() {
+        Bound::Included(&x) if actual > x => Err(Error::new(ErrorKind::TooManyFields {
+            expected: x,
+            actual,
+        })),
+        _ => Ok(()),
+    }
+}
+
+/// Parsing straight from the payload bytes.
+///
+/// Plain decimal numbers are handled without going through `str`; anything else falls back to
+/// `FromStr`, which also provides the error.
+pub(crate) trait FromField: Sized {
+    fn from_field(src: &[u8]) -> Result<Self>;
+}
+
+fn from_str<T: FromStr>(src: &[u8]) -> Result<T>
+where
+    Error: From<T::Err>,
+{
+    Ok(T::from_str(from_utf8(src)?)?)
+}
+
+fn digits(src: &[u8]) -> Option<u64> {
+    if src.is_empty() || src.len() > 19 {
+        return None;
+    }
+
+    src.iter().try_fold(0u64, |acc, &x| match x {
+        b'0'..=b'9' => Some(acc * 10 + u64::from(x - b'0')),
+        _ => None,
+    })
+}
+
+macro_rules! from_field_unsigned {
+    ($($t:ty),*) => {$(
+        impl FromField for $t {
+            fn from_field(src: &[u8]) -> Result<Self> {
+                match digits(src).and_then(|x| <$t>::try_from(x).ok()) {
+                    Some(x) => Ok(x),
+                    None => from_str(src),
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! from_field_signed {
+    ($($t:ty),*) => {$(
+        impl FromField for $t {
+            fn from_field(src: &[u8]) -> Result<Self> {
+                let value = match src.split_first() {
+                    Some((b'-', rest)) => digits(rest).and_then(|x| i64::try_from(x).ok()).map(|x| -x),
+                    _ => digits(src).and_then(|x| i64::try_from(x).ok()),
+                };
+
+                match value.and_then(|x| <$t>::try_from(x).ok()) {
+                    Some(x) => Ok(x),
+                    None => from_str(src),
+                }
+            }
+        }
+    )*};
+}
+
+from_field_unsigned!(u8, u16, u32, u64, usize);
+from_field_signed!(i32, i64);
+
+const POW10: [f32; 8] = [1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7];
+
+impl FromField for f32 {
+    fn from_field(src: &[u8]) -> Result<Self> {
+        let (negative, unsigned) = match src.split_first() {
+            Some((b'-', rest)) => (true, rest),
+            _ => (false, src),
+        };
+        let (int, frac) = match unsigned.iter().position(|&x| x == b'.') {
+            Some(index) => (&unsigned[..index], &unsigned[index + 1..]),
+            None => (unsigned, &b""[..]),
+        };
+
+        // Both the mantissa and the power of ten are exact in `f32` as long as they stay below
+        // 2^24, so the division is correctly rounded, just like `f32::from_str`.
+        if !int.is_empty() && int.len() + frac.len() <= 7 && frac.len() < POW10.len() {
+            let mantissa = match (digits(int), frac.is_empty()) {
+                (Some(int), true) => Some(int),
+                (Some(int), false) => digits(frac).map(|x| int * 10u64.pow(frac.len() as u32) + x),
+                _ => None,
+            };
+
+            if let Some(mantissa) = mantissa {
+                let value = mantissa as f32 / POW10[frac.len()];
+                return Ok(if negative { -value } else { value });
+            }
+        }
+
+        from_str(src)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{ErrorKind, Result};
+    use crate::tokenizer::{FromField, Tokenizer};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_tokenizer() -> Result<()> {
+        let mut fields = Tokenizer::new(b"230.0 -12 1 x", b' ', 4..)?;
+
+        assert_eq!(fields.parse::<f32>("voltage")?, 230.0);
+        assert_eq!(fields.parse::<i32>("current")?, -12);
+        assert!(fields.warning("flag")?);
+
+        let err = fields.parse::<usize>("power").unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::ParseInt(_)));
+        assert_eq!(err.field(), Some("power"));
+        assert_eq!(err.raw(), Some(&b"x"[..]));
+
+        let err = fields.skip("missing").unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 5,
+                actual: 4
+            }
+        ));
+        assert_eq!(err.field(), Some("missing"));
+
+        let err = Tokenizer::new(b"1 2", b' ', 3..=4).err().unwrap();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooFewFields {
+                expected: 3,
+                actual: 2
+            }
+        ));
+        let err = Tokenizer::new(b"1 2 3 4 5", b' ', 3..=4).err().unwrap();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TooManyFields {
+                expected: 4,
+                actual: 5
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_field_matches_from_str() {
+        for src in &[
+            "0",
+            "00.0",
+            "1",
+            "051.4",
+            "49.95",
+            "-3.2",
+            "230.0",
+            "83.3",
+            "1234567",
+            "0.0000001",
+            "12345678.9",
+            "1e3",
+            "+5",
+            ".5",
+            "5.",
+            "-",
+            "",
+            "abc",
+        ] {
+            assert_eq!(
+                f32::from_field(src.as_bytes()).ok(),
+                f32::from_str(src).ok(),
+                "{}",
+                src
+            );
+        }
+
+        for src in &[
+            "0",
+            "007",
+            "255",
+            "256",
+            "-1",
+            "+1",
+            "",
+            "18446744073709551616",
+        ] {
+            assert_eq!(u8::from_field(src.as_bytes()).ok(), u8::from_str(src).ok());
+            assert_eq!(
+                u64::from_field(src.as_bytes()).ok(),
+                u64::from_str(src).ok()
+            );
+            assert_eq!(
+                i32::from_field(src.as_bytes()).ok(),
+                i32::from_str(src).ok()
+            );
+        }
+    }
+}